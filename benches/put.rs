@@ -0,0 +1,51 @@
+//! Mesure le coût de `put` sur les trois chemins d'insertion : remplissage (une
+//! seule recherche dans la table), mise à jour d'une clé présente, et insertion
+//! avec éviction dans un cache plein.
+
+use cachelru::cache::{Cache, LRUCache};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CAPACITY: usize = 10_000;
+const ROUNDS: usize = 20;
+
+/// Exécute `run` `ROUNDS` fois sur un cache neuf préparé par `setup` et renvoie la
+/// meilleure durée par opération.
+fn measure(setup: impl Fn() -> Cache<u64, u64>, run: impl Fn(&mut Cache<u64, u64>)) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let mut cache = setup();
+            let start = Instant::now();
+            run(&mut cache);
+            black_box(&cache);
+            start.elapsed() / CAPACITY as u32
+        })
+        .min()
+        .unwrap()
+}
+
+fn fill(cache: &mut Cache<u64, u64>, offset: u64) {
+    for key in offset..offset + CAPACITY as u64 {
+        cache.put(black_box(key), key);
+    }
+}
+
+fn main() {
+    let empty = || Cache::new(CAPACITY);
+    let full = || {
+        let mut cache = Cache::new(CAPACITY);
+        fill(&mut cache, 0);
+        cache
+    };
+    let cases = [
+        ("remplissage", measure(empty, |cache| fill(cache, 0))),
+        ("mise à jour", measure(full, |cache| fill(cache, 0))),
+        (
+            "éviction",
+            measure(full, |cache| fill(cache, CAPACITY as u64)),
+        ),
+    ];
+    for (name, per_put) in cases {
+        println!("put ({name}) : {per_put:?} par opération");
+    }
+}
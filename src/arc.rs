@@ -0,0 +1,233 @@
+use crate::cache::{Cache, LRUCache};
+use std::hash::Hash;
+
+/// Un cache à remplacement adaptatif (ARC, Megiddo et Modha) : les entrées vues
+/// une seule fois (`t1`) et celles vues au moins deux fois (`t2`) sont tenues dans
+/// deux listes LRU distinctes, et deux listes fantômes (`b1`, `b2`) retiennent les
+/// clés récemment évincées de chacune. Un succès sur une clé fantôme déplace la
+/// cible `p` (taille visée pour `t1`) vers la liste qui en aurait eu besoin : le
+/// cache s'adapte ainsi entre récence et fréquence, et résiste aux parcours
+/// séquentiels qui videraient un cache LRU.
+///
+/// Seules les insertions comptent comme des demandes manquées : `get` sur une clé
+/// absente renvoie `None` sans rien enregistrer, et le `put` qui suit applique le
+/// traitement ARC (y compris pour une clé fantôme).
+#[derive(Debug)]
+pub struct ArcCache<K: Eq + Hash + Clone, V> {
+    capacity: usize,
+    p: usize,         // Taille visée pour `t1`, entre 0 et `capacity`
+    t1: Cache<K, V>,  // Entrées vues une fois
+    t2: Cache<K, V>,  // Entrées vues au moins deux fois
+    b1: Cache<K, ()>, // Clés récemment évincées de `t1`
+    b2: Cache<K, ()>, // Clés récemment évincées de `t2`
+}
+
+impl<K: Eq + Hash + Clone, V> ArcCache<K, V> {
+    /// Crée un nouvel `ArcCache` de `capacity` entrées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::arc::ArcCache;
+    /// use cachelru::cache::LRUCache;
+    ///
+    /// let mut cache = ArcCache::new(2);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        ArcCache {
+            capacity,
+            p: 0,
+            t1: Cache::new(capacity),
+            t2: Cache::new(capacity),
+            b1: Cache::new(capacity),
+            b2: Cache::new(capacity),
+        }
+    }
+
+    /// Renvoie le nombre d'entrées présentes dans le cache (hors clés fantômes).
+    pub fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Renvoie la capacité du cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Renvoie la taille visée pour la liste des entrées vues une seule fois : elle
+    /// augmente quand le cache aurait dû garder plus d'entrées récentes, diminue
+    /// quand il aurait dû garder plus d'entrées fréquentes.
+    pub fn recency_target(&self) -> usize {
+        self.p
+    }
+
+    /// Supprime une entrée du cache et renvoie sa valeur.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.t1.take(key).or_else(|| self.t2.take(key))
+    }
+
+    /// Évince l'entrée la moins récente de `t1` ou de `t2` selon la cible `p`, et
+    /// garde sa clé dans la liste fantôme correspondante.
+    fn replace(&mut self, in_b2: bool) {
+        let t1_len = self.t1.len();
+        let from_t1 = t1_len >= 1 && ((in_b2 && t1_len == self.p) || t1_len > self.p);
+        if from_t1 || self.t2.is_empty() {
+            demote(&mut self.t1, &mut self.b1);
+        } else {
+            demote(&mut self.t2, &mut self.b2);
+        }
+    }
+}
+
+/// Renvoie la clé la moins récemment utilisée d'un cache.
+fn lru_key<K: Eq + Hash + Clone, V>(cache: &Cache<K, V>) -> Option<K> {
+    cache.lru_n(1).first().map(|(key, _)| (*key).clone())
+}
+
+/// Évince l'entrée la moins récente de `list` et garde sa clé dans `ghost`.
+fn demote<K: Eq + Hash + Clone, V>(list: &mut Cache<K, V>, ghost: &mut Cache<K, ()>) {
+    if let Some(key) = lru_key(list) {
+        list.take(&key);
+        ghost.put(key, ());
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> LRUCache<K, V> for ArcCache<K, V> {
+    /// Insère une paire clé-valeur en appliquant le traitement ARC.
+    fn put(&mut self, key: K, value: V) {
+        let c = self.capacity;
+        if c == 0 {
+            return;
+        }
+
+        // Cas I : entrée présente, elle rejoint les entrées fréquentes
+        if self.t1.take(&key).is_some() || self.t2.take(&key).is_some() {
+            self.t2.put(key, value);
+            return;
+        }
+
+        // Cas II : clé fantôme de `t1`, le cache aurait dû garder plus d'entrées récentes
+        if self.b1.take(&key).is_some() {
+            let delta = (self.b2.len() / (self.b1.len() + 1)).max(1);
+            self.p = (self.p + delta).min(c);
+            self.replace(false);
+            self.t2.put(key, value);
+            return;
+        }
+
+        // Cas III : clé fantôme de `t2`, le cache aurait dû garder plus d'entrées fréquentes
+        if self.b2.take(&key).is_some() {
+            let delta = (self.b1.len() / (self.b2.len() + 1)).max(1);
+            self.p = self.p.saturating_sub(delta);
+            self.replace(true);
+            self.t2.put(key, value);
+            return;
+        }
+
+        // Cas IV : clé inconnue
+        let l1 = self.t1.len() + self.b1.len();
+        let total = l1 + self.t2.len() + self.b2.len();
+        if l1 == c {
+            if self.t1.len() < c {
+                if let Some(ghost) = lru_key(&self.b1) {
+                    self.b1.take(&ghost);
+                }
+                self.replace(false);
+            } else if let Some(oldest) = lru_key(&self.t1) {
+                self.t1.take(&oldest);
+            }
+        } else if total >= c {
+            if total == 2 * c {
+                if let Some(ghost) = lru_key(&self.b2) {
+                    self.b2.take(&ghost);
+                }
+            }
+            self.replace(false);
+        }
+        self.t1.put(key, value);
+    }
+
+    /// Récupère une valeur du cache par sa clé ; un succès fait rejoindre à
+    /// l'entrée les entrées fréquentes.
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if let Some(value) = self.t1.take(key) {
+            self.t2.put(key.clone(), value);
+        }
+        self.t2.get(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arc_resists_scans() {
+        let mut arc = ArcCache::new(4);
+        let mut lru = Cache::new(4);
+        // Deux entrées chaudes, lues après leur insertion...
+        for key in [1, 2] {
+            arc.put(key, key);
+            lru.put(key, key);
+            arc.get(&key);
+            lru.get(&key);
+        }
+        // ...puis un parcours de clés vues une seule fois
+        for key in 100..120 {
+            arc.put(key, key);
+            lru.put(key, key);
+        }
+
+        assert_eq!(arc.get(&1), Some(&1));
+        assert_eq!(arc.get(&2), Some(&2));
+        assert_eq!(lru.get(&1), None);
+        assert_eq!(lru.get(&2), None);
+        assert_eq!(arc.len(), 4);
+    }
+
+    #[test]
+    fn test_arc_adapts_target() {
+        let mut cache = ArcCache::new(4);
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.get(&1);
+        cache.get(&2);
+        cache.put(3, 3);
+        cache.put(4, 4);
+        // Cache plein : "5" évince "3", dont la clé devient fantôme
+        cache.put(5, 5);
+        assert_eq!(cache.get(&3), None);
+        assert_eq!(cache.recency_target(), 0);
+
+        // Redemander "3" révèle qu'il fallait plus de place pour les entrées récentes
+        cache.put(3, 3);
+        assert_eq!(cache.recency_target(), 1);
+        assert_eq!(cache.get(&3), Some(&3));
+        assert_eq!(cache.len(), 4);
+
+        // Une nouvelle clé évince cette fois l'entrée fréquente la plus ancienne, "1"
+        cache.put(10, 10);
+        assert_eq!(cache.get(&1), None);
+
+        // La redemander révèle qu'il fallait plus de place pour les entrées fréquentes
+        cache.put(1, 1);
+        assert_eq!(cache.recency_target(), 0);
+        assert_eq!(cache.get(&1), Some(&1));
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn test_arc_zero_capacity() {
+        let mut cache = ArcCache::new(0);
+        cache.put("A", 1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"A"), None);
+    }
+}
@@ -1,8 +1,11 @@
 use cachelru::cache::{Cache, LRUCache};
 use std::time::Instant;
 
+// Graine fixe : le hachage, et donc les mesures, ne varient pas d'une exécution à l'autre
+const SEED: u64 = 0x5eed;
+
 fn main() {
-    let mut cache = Cache::new(1000);
+    let mut cache = Cache::with_seed(1000, SEED);
 
     let start = Instant::now();
     for i in 0..1000 {
@@ -17,4 +20,65 @@ fn main() {
     }
     let duration = start.elapsed();
     println!("Temps pour accéder à 1000 éléments : {:?}", duration);
+
+    // Montée en charge : remplissage d'un grand cache vide
+    let mut cache = Cache::with_seed(50_000, SEED);
+    let start = Instant::now();
+    for i in 0..50_000 {
+        cache.put(i, i);
+    }
+    let duration = start.elapsed();
+    println!(
+        "Temps pour remplir un cache de 50000 éléments : {:?}",
+        duration
+    );
+
+    // Clés longues : le coût des clones de clés devient visible
+    let keys: Vec<String> = (0..10_000).map(|i| format!("{:0>256}", i)).collect();
+    let mut cache = Cache::with_seed(5_000, SEED);
+
+    let start = Instant::now();
+    for key in &keys {
+        cache.put(key.clone(), 0);
+    }
+    let duration = start.elapsed();
+    println!(
+        "Temps pour insérer 10000 clés de 256 octets (avec évictions) : {:?}",
+        duration
+    );
+
+    let start = Instant::now();
+    for _ in 0..10 {
+        for key in &keys[5_000..] {
+            cache.get(key);
+        }
+    }
+    let duration = start.elapsed();
+    println!(
+        "Temps pour accéder 50000 fois à des clés de 256 octets : {:?}",
+        duration
+    );
+
+    // Lectures dispersées sur un grand cache : chaque promotion touche la liste
+    for threshold in [1, 8] {
+        let mut cache = Cache::with_seed(100_000, SEED);
+        cache.set_promote_threshold(threshold);
+        for i in 0..100_000u64 {
+            cache.put(i, i);
+        }
+        let start = Instant::now();
+        // Générateur congruentiel : suite de clés pseudo-aléatoire et reproductible
+        let mut state = 1u64;
+        for _ in 0..1_000_000 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            cache.get(&((state >> 33) % 100_000));
+        }
+        let duration = start.elapsed();
+        println!(
+            "Temps pour 1000000 accès dispersés (promotion tous les {} accès) : {:?}",
+            threshold, duration
+        );
+    }
 }
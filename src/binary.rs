@@ -0,0 +1,197 @@
+//! Sérialisation binaire compacte, sans dépendance externe : les clés et les
+//! valeurs se convertissent en octets via les traits [`ToBytes`] et [`FromBytes`].
+//!
+//! Format (entiers en petit-boutiste) : la capacité et le nombre d'entrées sur
+//! 8 octets chacun, puis pour chaque entrée, de la plus récemment utilisée à la
+//! moins récente, la longueur de la clé sur 8 octets, la clé, la longueur de la
+//! valeur sur 8 octets et la valeur.
+
+use crate::cache::Cache;
+use std::hash::Hash;
+use std::io;
+
+/// Conversion d'une valeur en octets pour `Cache::serialize_compact`.
+pub trait ToBytes {
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+/// Reconstruction d'une valeur à partir des octets produits par [`ToBytes`].
+pub trait FromBytes: Sized {
+    /// Renvoie `None` si les octets ne représentent pas une valeur valide.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_bytes_for_int {
+    ($($t:ty),*) => {$(
+        impl ToBytes for $t {
+            fn to_bytes(&self) -> Vec<u8> {
+                self.to_le_bytes().to_vec()
+            }
+        }
+
+        impl FromBytes for $t {
+            fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                Some(<$t>::from_le_bytes(bytes.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+impl_bytes_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl ToBytes for String {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl FromBytes for String {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+impl ToBytes for Vec<u8> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+impl FromBytes for Vec<u8> {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Cache<K, V> {
+    /// Sérialise la capacité et les entrées du cache dans un bloc binaire compact,
+    /// en conservant l'ordre d'utilisation. Les échéances, poids et réglages du
+    /// cache ne sont pas conservés.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, String> = Cache::new(3);
+    /// cache.put(1, "un".to_string());
+    /// let bytes = cache.serialize_compact();
+    /// let mut restored: Cache<u32, String> = Cache::deserialize_compact(&bytes).unwrap();
+    /// assert_eq!(restored.get(&1), Some(&"un".to_string()));
+    /// ```
+    pub fn serialize_compact(&self) -> Vec<u8>
+    where
+        K: ToBytes,
+        V: ToBytes,
+    {
+        let entries = self.mru_n(self.len());
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.capacity() as u64).to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (key, value) in entries {
+            for bytes in [key.to_bytes(), value.to_bytes()] {
+                out.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+                out.extend_from_slice(&bytes);
+            }
+        }
+        out
+    }
+
+    /// Reconstruit un cache à partir d'un bloc produit par `serialize_compact`.
+    ///
+    /// Renvoie une erreur `InvalidData` si le bloc est tronqué, contient des octets
+    /// en trop ou une clé ou une valeur invalide.
+    pub fn deserialize_compact(bytes: &[u8]) -> io::Result<Self>
+    where
+        K: FromBytes,
+        V: FromBytes,
+    {
+        let mut reader = Reader(bytes);
+        let capacity = reader.length()?;
+        let count = reader.length()?;
+        // Le nombre annoncé ne sert pas à réserver : un bloc corrompu ne doit pas
+        // provoquer d'allocation démesurée
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let key = K::from_bytes(reader.chunk()?).ok_or_else(|| invalid("clé invalide"))?;
+            let value = V::from_bytes(reader.chunk()?).ok_or_else(|| invalid("valeur invalide"))?;
+            entries.push((key, value));
+        }
+        if !reader.0.is_empty() {
+            return Err(invalid("octets en trop après la dernière entrée"));
+        }
+        Ok(Cache::from_parts(capacity, entries))
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Lecture séquentielle d'un bloc binaire.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(invalid("bloc binaire tronqué"));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn length(&mut self) -> io::Result<usize> {
+        let bytes = self.take(8)?.try_into().unwrap();
+        usize::try_from(u64::from_le_bytes(bytes)).map_err(|_| invalid("longueur démesurée"))
+    }
+
+    fn chunk(&mut self) -> io::Result<&'a [u8]> {
+        let len = self.length()?;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::LRUCache;
+
+    #[test]
+    fn test_compact_round_trip() {
+        let mut cache: Cache<u32, String> = Cache::new(5);
+        cache.put(1, "un".to_string());
+        cache.put(2, String::new());
+        cache.put(3, "trois, é".to_string());
+        cache.put(4, "quatre".to_string());
+        cache.get(&2);
+
+        let bytes = cache.serialize_compact();
+        let restored: Cache<u32, String> = Cache::deserialize_compact(&bytes).unwrap();
+        assert_eq!(restored.capacity(), 5);
+        assert_eq!(restored.mru_n(5), cache.mru_n(5));
+        assert_eq!(
+            restored
+                .mru_n(5)
+                .iter()
+                .map(|(k, _)| **k)
+                .collect::<Vec<_>>(),
+            vec![2, 4, 3, 1]
+        );
+    }
+
+    #[test]
+    fn test_compact_rejects_corrupted_blobs() {
+        let mut cache: Cache<u32, String> = Cache::new(2);
+        cache.put(1, "un".to_string());
+        let bytes = cache.serialize_compact();
+
+        let truncated = Cache::<u32, String>::deserialize_compact(&bytes[..bytes.len() - 1]);
+        assert_eq!(truncated.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(Cache::<u32, String>::deserialize_compact(&extra).is_err());
+        // Une clé `u32` doit tenir sur quatre octets exactement
+        assert!(Cache::<u64, String>::deserialize_compact(&bytes).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Filtre de Bloom à compteurs, utilisé par le cache pour écarter sans consulter
+//! la table les clés certainement absentes.
+//!
+//! Chaque clé incrémente `HASHES` compteurs choisis à partir de son hachage ; une
+//! clé dont l'un des compteurs est nul n'a jamais été insérée (ou a été retirée).
+//! Les compteurs permettent les suppressions : un compteur saturé n'est plus jamais
+//! décrémenté, ce qui peut ajouter des faux positifs mais jamais de faux négatif.
+
+/// Nombre de compteurs touchés par clé.
+const HASHES: u64 = 4;
+
+#[derive(Debug, Clone)]
+pub(crate) struct BloomFilter {
+    counters: Vec<u8>,
+}
+
+impl BloomFilter {
+    /// Crée un filtre vide de `size` compteurs (au moins un).
+    pub(crate) fn new(size: usize) -> Self {
+        BloomFilter {
+            counters: vec![0; size.max(1)],
+        }
+    }
+
+    /// Enregistre une clé à partir de son hachage.
+    pub(crate) fn insert(&mut self, hash: u64) {
+        for position in self.positions(hash) {
+            let counter = &mut self.counters[position];
+            *counter = counter.saturating_add(1);
+        }
+    }
+
+    /// Retire une clé enregistrée par `insert`.
+    pub(crate) fn remove(&mut self, hash: u64) {
+        for position in self.positions(hash) {
+            let counter = &mut self.counters[position];
+            // Un compteur saturé a perdu le compte exact : il reste saturé
+            if *counter != u8::MAX {
+                *counter = counter.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Indique si la clé a pu être enregistrée ; `false` est une absence certaine.
+    pub(crate) fn may_contain(&self, hash: u64) -> bool {
+        self.positions(hash)
+            .all(|position| self.counters[position] > 0)
+    }
+
+    /// Oublie toutes les clés.
+    pub(crate) fn clear(&mut self) {
+        self.counters.fill(0);
+    }
+
+    /// Renvoie les positions des compteurs d'une clé, par double hachage.
+    fn positions(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let size = self.counters.len() as u64;
+        let (h1, h2) = (hash, hash.rotate_left(32) | 1);
+        (0..HASHES).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % size) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturated_counters_never_forget() {
+        let mut filter = BloomFilter::new(1);
+        // Un seul compteur : toutes les clés le partagent et finissent par le saturer
+        for hash in 0..300 {
+            filter.insert(hash);
+        }
+        for hash in 0..299 {
+            filter.remove(hash);
+        }
+        assert!(filter.may_contain(299));
+
+        filter.clear();
+        assert!(!filter.may_contain(299));
+    }
+}
@@ -1,8 +1,14 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use crate::bloom::BloomFilter;
+use std::collections::hash_map::{DefaultHasher, Entry, RandomState};
+use std::collections::{HashMap, HashSet, TryReserveError, VecDeque};
+use std::fmt;
 use std::fs::File;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::io::{self, BufRead, BufReader, Write};
 use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Trait définissant les opérations d'un cache LRU.
 pub trait LRUCache<K, V> {
@@ -13,25 +19,303 @@ pub trait LRUCache<K, V> {
     fn get(&mut self, key: &K) -> Option<&V>;
 }
 
+/// Stratégie d'éviction utilisée lorsque le cache est plein.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// LRU strict : chaque accès déplace l'entrée en tête de liste.
+    #[default]
+    Lru,
+    /// Approximation CLOCK (seconde chance) : un accès positionne seulement un bit
+    /// de référence, sans modifier la liste. Lors d'une éviction, l'aiguille part de
+    /// la queue et efface les bits rencontrés jusqu'à trouver une entrée non référencée.
+    Clock,
+    /// LFU : l'entrée la moins fréquemment utilisée est évincée. En cas d'égalité,
+    /// la victime est choisie selon le [`LfuTiebreak`] du cache.
+    Lfu,
+}
+
+/// Départage des entrées de même fréquence minimale en mode LFU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LfuTiebreak {
+    /// Évince la moins récemment utilisée des entrées à égalité.
+    #[default]
+    OldestFirst,
+    /// Évince la plus récemment utilisée des entrées à égalité.
+    NewestFirst,
+}
+
+/// Changement structurel du cache, diffusé aux abonnés de `subscribe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheEvent {
+    /// La capacité est passée de `old` à `new` entrées.
+    Resize { old: usize, new: usize },
+    /// Toutes les entrées ont été supprimées.
+    Clear,
+}
+
+/// Traitement, lors d'un chargement depuis plusieurs fichiers, des clés déjà
+/// présentes dans le cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// La valeur déjà présente est conservée, à sa position.
+    KeepExisting,
+    /// La valeur chargée remplace la valeur présente et devient la plus récente.
+    #[default]
+    Overwrite,
+}
+
+/// Traitement des clés apparaissant plusieurs fois dans un fichier chargé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// La dernière occurrence l'emporte (et fixe la position de l'entrée).
+    #[default]
+    LastWins,
+    /// La première occurrence l'emporte, les suivantes sont ignorées.
+    FirstWins,
+    /// Le chargement échoue avec une erreur `InvalidData`, sans rien insérer.
+    Error,
+}
+
+/// Différences entre deux caches, calculées par `Cache::diff`. Chaque liste suit
+/// l'ordre d'utilisation du cache d'où proviennent ses clés, de la plus récemment
+/// utilisée à la moins récente.
+#[derive(Debug, PartialEq)]
+pub struct CacheDiff<'a, K, V> {
+    /// Clés présentes seulement dans le cache interrogé.
+    pub only_in_self: Vec<&'a K>,
+    /// Clés présentes seulement dans l'autre cache.
+    pub only_in_other: Vec<&'a K>,
+    /// Clés présentes des deux côtés avec des valeurs différentes : la clé, la
+    /// valeur du cache interrogé, puis celle de l'autre cache.
+    pub differing: Vec<(&'a K, &'a V, &'a V)>,
+}
+
+/// Statistiques de consultation, renvoyées par `Cache::stats`.
+///
+/// Chaque recherche d'une clé à travers le cache (`get`, `get_entry`, les
+/// variantes de `get_or_insert_with`...) compte comme un succès si l'entrée est
+/// présente et non expirée, comme un échec sinon. Les lectures qui ne passent pas
+/// par l'ordre d'utilisation (`peek_or`, `ttl_remaining`...) ne comptent pas, pas
+/// plus que les opérations qui ne cherchent une clé que pour la modifier (`pin`,
+/// `peek_mut_if`, `update_in_place`, `touch`, `prefetch`...). Les
+/// compteurs s'accumulent depuis la création du cache ou le dernier
+/// `reset_stats` ; `clear` ne les remet pas à zéro, et `resize` seulement si
+/// `set_reset_stats_on_resize` l'a demandé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Recherches ayant trouvé une entrée valide.
+    pub hits: u64,
+    /// Recherches n'ayant trouvé aucune entrée, ou une entrée expirée.
+    pub misses: u64,
+}
+
 /// Un nœud dans la liste doublement chaînée pour suivre l'ordre d'utilisation.
-#[derive(Debug)]
-struct Node<K> {
-    prev: Option<K>,
-    next: Option<K>,
+///
+/// Les nœuds sont rangés dans un tableau d'emplacements et les liens `prev`/`next`
+/// sont des indices dans ce tableau : mettre la liste à jour ne clone jamais de clé.
+#[derive(Debug, Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    prev: Option<usize>,
+    next: Option<usize>,
+    referenced: bool,             // Bit de référence utilisé par la politique CLOCK
+    weight: usize,                // Poids calculé à l'insertion (0 sans pondérateur)
+    expires_at: Option<Instant>,  // Échéance de l'entrée (aucune sans TTL)
+    frequency: usize,             // Nombre d'utilisations, pour la politique LFU
+    hits: usize,                  // Accès depuis la dernière promotion (mode LRU)
+    modified: u64,                // Génération de la dernière modification de la valeur
+    pinned: bool,                 // Épinglée avec `pin` : jamais évincée
+    last_access: Option<Instant>, // Dernière insertion ou lecture, si les accès sont datés
+    access_count: usize,          // Accès depuis l'insertion, quelle que soit la politique
+    priority: u8,                 // Priorité de `put_with_priority` (0 par défaut)
+}
+
+type WeighFn<K, V> = dyn Fn(&K, &V) -> usize + Send + Sync;
+
+/// Fonction de pondération associant un poids à chaque entrée.
+struct Weigher<K, V>(Box<WeighFn<K, V>>);
+
+impl<K, V> fmt::Debug for Weigher<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Weigher")
+    }
+}
+
+/// Prédicat d'épinglage accepté par `Cache::set_pin_predicate`.
+pub type PinFn<K, V> = dyn Fn(&K, &V) -> bool + Send + Sync;
+
+/// Prédicat désignant les entrées que l'éviction doit épargner.
+struct PinPredicate<K, V>(Box<PinFn<K, V>>);
+
+impl<K, V> fmt::Debug for PinPredicate<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PinPredicate")
+    }
+}
+
+/// Formatage des clés dans les traces de la fonctionnalité `logging`.
+#[cfg(feature = "logging")]
+type KeyFmt<K> = fn(&K, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+/// Clé telle qu'elle figure dans une trace : formatée avec `Debug` si
+/// `Cache::log_keys` l'a permis, remplacée par `_` sinon.
+#[cfg(feature = "logging")]
+struct LoggedKey<'a, K>(&'a K, Option<KeyFmt<K>>);
+
+#[cfg(feature = "logging")]
+impl<K> fmt::Display for LoggedKey<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Some(fmt_key) => fmt_key(self.0, f),
+            None => f.write_str("_"),
+        }
+    }
+}
+
+type ClockFn = dyn Fn() -> Instant + Send + Sync;
+
+/// Horloge utilisée pour les durées de vie ; injectable pour les tests.
+#[derive(Clone)]
+struct Clock(Arc<ClockFn>);
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
+
+/// Croissance automatique configurée par `Cache::set_autogrow`, avec la fenêtre
+/// d'observation en cours.
+#[derive(Debug, Clone, Copy)]
+struct Autogrow {
+    max_capacity: usize,
+    growth_factor: f64,
+    puts: usize,   // Insertions consécutives trouvant le cache plein
+    misses: usize, // Parmi elles, celles d'une nouvelle clé
+}
+
+/// Nombre maximal d'entrées pour lesquelles la place est réservée à la création :
+/// au-delà, la table grandit au fil des insertions plutôt que d'allouer d'emblée
+/// une capacité démesurée.
+const PRESIZE_LIMIT: usize = 1 << 16;
+
+/// Version du format texte écrit par `Cache::save_to_file`, annoncée en tête de
+/// fichier par la ligne `#cachelru v1`. Un fichier sans en-tête est lu comme un
+/// fichier de la version 1 ; tous les chargements depuis un fichier vérifient cet
+/// en-tête.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Début de la ligne d'en-tête d'un fichier de cache, suivi du numéro de version.
+const FORMAT_HEADER: &str = "#cachelru v";
+
+/// Ouvre un fichier de cache et consomme sa ligne d'en-tête éventuelle (voir
+/// `read_format_header`).
+fn open_cache_file(filename: &str) -> io::Result<BufReader<File>> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    read_format_header(&mut reader)?;
+    Ok(reader)
+}
+
+/// Consomme la ligne d'en-tête éventuelle d'un contenu de fichier de cache, en
+/// refusant une version de format que cette version de la crate ne sait pas lire.
+fn read_format_header<R: BufRead>(reader: &mut R) -> io::Result<()> {
+    if reader.fill_buf()?.starts_with(b"#cachelru") {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        let version = header
+            .strip_prefix(FORMAT_HEADER)
+            .and_then(|version| version.parse::<u32>().ok());
+        if version != Some(FORMAT_VERSION) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("version de format de cache non prise en charge : « {header} »"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Générateur de hachage de la table : aléatoire par défaut, ou à graine fixe pour
+/// obtenir un comportement reproductible d'une exécution à l'autre.
+#[derive(Debug, Clone)]
+enum HashState {
+    Random(RandomState),
+    Seeded(u64),
+}
+
+impl Default for HashState {
+    fn default() -> Self {
+        HashState::Random(RandomState::new())
+    }
+}
+
+impl BuildHasher for HashState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        match self {
+            HashState::Random(state) => state.build_hasher(),
+            HashState::Seeded(seed) => {
+                // Les clés de `DefaultHasher::new` sont fixes : la graine suffit à
+                // différencier les tables
+                let mut hasher = DefaultHasher::new();
+                hasher.write_u64(*seed);
+                hasher
+            }
+        }
+    }
 }
 
 /// Un cache LRU générique.
-#[derive(Debug)]
 pub struct Cache<K: Eq + Hash + Clone, V> {
     capacity: usize,
-    map: HashMap<K, (V, Node<K>)>,
-    head: Option<K>, // Le plus récemment utilisé
-    tail: Option<K>, // Le moins récemment utilisé
+    policy: EvictionPolicy,
+    lfu_tiebreak: LfuTiebreak,
+    duplicate_policy: DuplicatePolicy,
+    map: HashMap<K, usize, HashState>, // Clé -> indice de son emplacement
+    slots: Vec<Option<Node<K, V>>>,    // Emplacements des nœuds
+    free: Vec<usize>,                  // Emplacements libres, réutilisés en priorité
+    head: Option<usize>,               // Le plus récemment utilisé
+    tail: Option<usize>,               // Le moins récemment utilisé
+    weigher: Option<Weigher<K, V>>,
+    max_weight: usize,
+    total_weight: usize,
+    clock: Clock,
+    negative: HashMap<K, Instant, HashState>, // Clés connues comme absentes -> échéance
+    negative_ttl: Option<Duration>,           // Cache négatif désactivé si `None`
+    promote_threshold: usize,                 // Accès nécessaires à une promotion LRU
+    accesses: u64,                            // Nombre total d'accès (lectures et insertions)
+    stats: CacheStats,                        // Succès et échecs des recherches
+    reset_stats_on_resize: bool,              // `resize` remet `stats` à zéro
+    idle_check: (u64, Instant),               // Accès constatés par `maybe_shrink`, et quand
+    timed_accesses: bool,                     // Chaque accès est daté dans `last_access`
+    generation: u64,                          // Compteur de modifications, croissant
+    checkpointed: u64,                        // Génération du dernier `checkpoint_to_file`
+    flushed: u64,                             // Génération du dernier `flush_dirty`
+    subscribers: Vec<Sender<CacheEvent>>,     // Abonnés aux changements structurels
+    pin_predicate: Option<PinPredicate<K, V>>,
+    rejected: Option<V>, // Dernière valeur refusée faute de capacité, renvoyée par référence
+    autogrow: Option<Autogrow>,
+    bloom: Option<BloomFilter>, // Clés présentes, pour écarter vite les absences
+    prioritized: bool,          // Une priorité a été donnée : l'éviction en tient compte
+    watermarks: Option<(usize, usize)>, // Seuils (haut, bas) de l'éviction par lots
+    evictions: Option<Sender<(K, V)>>, // Entrées évincées, pour `eviction_channel`
+    #[cfg(feature = "logging")]
+    key_fmt: Option<KeyFmt<K>>, // Formatage des clés dans les traces, donné par `log_keys`
 }
 
 impl<K: Eq + Hash + Clone, V> Cache<K, V> {
     /// Crée un nouveau `Cache` avec une capacité donnée.
     ///
+    /// La place nécessaire est réservée d'emblée, dans la limite de 65 536 entrées :
+    /// le remplissage du cache ne provoque pas de réallocation de la table.
+    ///
+    /// Une capacité nulle donne un cache désactivé, qui n'alloue rien et refuse
+    /// toute nouvelle entrée jusqu'à ce qu'un `resize` lui donne une capacité ; la
+    /// table s'agrandit alors au fil des insertions.
+    ///
     /// # Exemple
     ///
     /// ```
@@ -40,14 +324,131 @@ impl<K: Eq + Hash + Clone, V> Cache<K, V> {
     /// let mut cache: Cache<&str, i32> = Cache::new(3);
     /// ```
     pub fn new(capacity: usize) -> Self {
+        Cache::with_policy(capacity, EvictionPolicy::Lru)
+    }
+
+    /// Crée un nouveau `Cache` avec une capacité et une stratégie d'éviction données.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, EvictionPolicy};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::with_policy(3, EvictionPolicy::Clock);
+    /// ```
+    pub fn with_policy(capacity: usize, policy: EvictionPolicy) -> Self {
         Cache {
             capacity,
-            map: HashMap::new(),
+            policy,
+            lfu_tiebreak: LfuTiebreak::default(),
+            duplicate_policy: DuplicatePolicy::default(),
+            map: HashMap::with_capacity_and_hasher(
+                capacity.min(PRESIZE_LIMIT),
+                HashState::default(),
+            ),
+            slots: Vec::with_capacity(capacity.min(PRESIZE_LIMIT)),
+            free: Vec::new(),
             head: None,
             tail: None,
+            weigher: None,
+            max_weight: usize::MAX,
+            total_weight: 0,
+            clock: Clock(Arc::new(Instant::now)),
+            negative: HashMap::default(),
+            negative_ttl: None,
+            promote_threshold: 1,
+            accesses: 0,
+            stats: CacheStats::default(),
+            reset_stats_on_resize: false,
+            idle_check: (0, Instant::now()),
+            timed_accesses: false,
+            generation: 0,
+            checkpointed: 0,
+            flushed: 0,
+            subscribers: Vec::new(),
+            pin_predicate: None,
+            rejected: None,
+            autogrow: None,
+            bloom: None,
+            prioritized: false,
+            watermarks: None,
+            evictions: None,
+            #[cfg(feature = "logging")]
+            key_fmt: None,
         }
     }
 
+    /// Crée un nouveau `Cache` dont la table est hachée avec une graine fixe : deux
+    /// caches créés avec la même graine se comportent de façon identique pour une
+    /// même suite d'opérations, ce qui rend les tests et les mesures reproductibles.
+    ///
+    /// Le hachage devient prévisible : à éviter pour des clés contrôlées par un tiers.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::with_seed(3, 42);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// ```
+    pub fn with_seed(capacity: usize, seed: u64) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.map =
+            HashMap::with_capacity_and_hasher(capacity.min(PRESIZE_LIMIT), HashState::Seeded(seed));
+        cache
+    }
+
+    /// Crée un cache de capacité 1, qui ne garde que la dernière entrée insérée
+    /// (mémorisation du dernier résultat, par exemple). L'unique nœud est réutilisé
+    /// d'une insertion à l'autre, sans manipulation de la liste.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::singleton();
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.get(&"A"), None);
+    /// assert_eq!(cache.get(&"B"), Some(&2));
+    /// ```
+    pub fn singleton() -> Self {
+        Cache::new(1)
+    }
+
+    /// Crée un nouveau `Cache` pondéré : en plus de la capacité en nombre d'entrées,
+    /// la somme des poids calculés par `weigher` ne peut pas dépasser `max_weight`.
+    ///
+    /// Les deux limites s'appliquent en même temps, sans priorité entre elles : la
+    /// première atteinte (nombre d'entrées ou poids total) déclenche l'éviction, et
+    /// les entrées sont évincées jusqu'à ce que la nouvelle entrée respecte les deux.
+    /// Une entrée plus lourde que `max_weight` à elle seule est tout de même
+    /// conservée, seule dans le cache.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, String> = Cache::with_weigher(10, 8, |_, v: &String| v.len());
+    /// cache.put("A", "abcd".to_string());
+    /// cache.put("B", "efgh".to_string());
+    /// cache.put("C", "ij".to_string()); // Évince "A"
+    /// assert_eq!(cache.total_weight(), 6);
+    /// ```
+    pub fn with_weigher<F>(capacity: usize, max_weight: usize, weigher: F) -> Self
+    where
+        F: Fn(&K, &V) -> usize + Send + Sync + 'static,
+    {
+        let mut cache = Cache::new(capacity);
+        cache.weigher = Some(Weigher(Box::new(weigher)));
+        cache.max_weight = max_weight;
+        cache
+    }
+
     /// Crée un nouveau `Cache` persistant avec une capacité donnée et un fichier de stockage.
     ///
     /// # Exemple
@@ -63,24 +464,59 @@ impl<K: Eq + Hash + Clone, V> Cache<K, V> {
         V: std::fmt::Display + std::str::FromStr,
     {
         let mut cache = Cache::new(capacity);
-        cache.load_from_file(filename).unwrap_or_else(|_| ());
+        cache.load_from_file(filename).unwrap_or(());
         cache
     }
 
-    /// Sauvegarde le cache dans un fichier.
+    /// Sauvegarde le cache dans un fichier. La première ligne, `#cachelru v1`,
+    /// indique la version du format (voir [`FORMAT_VERSION`]) ; les entrées suivent
+    /// au format de `save_to_writer`.
     pub fn save_to_file(&self, filename: &str) -> io::Result<()>
     where
         K: std::fmt::Display,
         V: std::fmt::Display,
     {
-        let mut file = File::create(filename)?;
-        for (key, (value, _)) in &self.map {
-            writeln!(file, "{}\t{}", key, value)?;
+        let mut writer = io::BufWriter::new(File::create(filename)?);
+        writeln!(writer, "{}{}", FORMAT_HEADER, FORMAT_VERSION)?;
+        self.save_to_writer(writer)
+    }
+
+    /// Écrit le contenu du cache dans n'importe quelle destination implémentant `Write`.
+    ///
+    /// Chaque entrée est écrite sur une ligne au format `clé\tvaleur`, de la moins
+    /// récemment utilisée à la plus récemment utilisée : un rechargement restitue
+    /// ainsi l'ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<String, String> = Cache::new(3);
+    /// cache.put("A".to_string(), "value_a".to_string());
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// cache.save_to_writer(&mut buffer).unwrap();
+    /// assert_eq!(buffer, b"A\tvalue_a\n");
+    /// ```
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+        V: std::fmt::Display,
+    {
+        for index in self.indices_from_tail() {
+            let node = self.node(index);
+            writeln!(writer, "{}\t{}", node.key, node.value)?;
         }
-        Ok(())
+        writer.flush()
     }
 
     /// Charge le cache depuis un fichier.
+    ///
+    /// Le fichier peut être annoté à la main : les lignes vides et celles qui
+    /// commencent par `#` sont ignorées (voir `load_from_reader`). Un en-tête de
+    /// version (`#cachelru v1`) est vérifié : une version que cette crate ne sait
+    /// pas lire produit une erreur `InvalidData`, et rien n'est chargé.
     pub fn load_from_file(&mut self, filename: &str) -> io::Result<()>
     where
         K: std::fmt::Display + std::str::FromStr,
@@ -89,193 +525,5908 @@ impl<K: Eq + Hash + Clone, V> Cache<K, V> {
         if !Path::new(filename).exists() {
             return Ok(());
         }
-        let file = File::open(filename)?;
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
-            let line = line?;
-            let mut parts = line.split('\t');
-            if let (Some(k_str), Some(v_str)) = (parts.next(), parts.next()) {
-                if let (Ok(key), Ok(value)) = (k_str.parse::<K>(), v_str.parse::<V>()) {
-                    self.put(key, value);
-                }
-            }
-        }
-        Ok(())
+        self.load_from_reader(open_cache_file(filename)?)
     }
 
-    /// Supprime un nœud de sa position actuelle dans la liste.
-    fn remove_node(&mut self, key: &K) {
-        let node = self.map.get(key).unwrap();
-        let prev_key_opt = node.1.prev.clone();
-        let next_key_opt = node.1.next.clone();
-
-        if let Some(ref prev_key) = prev_key_opt {
-            let prev_node = self.map.get_mut(prev_key).unwrap();
-            prev_node.1.next = next_key_opt.clone();
-        } else {
-            self.head = next_key_opt.clone();
-        }
-
-        if let Some(ref next_key) = next_key_opt {
-            let next_node = self.map.get_mut(next_key).unwrap();
-            next_node.1.prev = prev_key_opt.clone();
-        } else {
-            self.tail = prev_key_opt.clone();
-        }
+    /// Version asynchrone de `save_to_file`, pour ne pas bloquer un runtime Tokio
+    /// (fonctionnalité `tokio`). Le contenu est préparé en mémoire, dans le même
+    /// format, puis écrit avec `tokio::fs`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut cache: Cache<String, u32> = Cache::new(3);
+    /// cache.put("A".to_string(), 1);
+    /// cache.save_to_file_async("cache_async.txt").await.unwrap();
+    ///
+    /// let mut reloaded: Cache<String, u32> = Cache::new(3);
+    /// reloaded.load_from_file_async("cache_async.txt").await.unwrap();
+    /// assert_eq!(reloaded.get(&"A".to_string()), Some(&1));
+    /// # std::fs::remove_file("cache_async.txt").unwrap();
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn save_to_file_async(&self, filename: &str) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+        V: std::fmt::Display,
+    {
+        let mut buffer = format!("{}{}\n", FORMAT_HEADER, FORMAT_VERSION).into_bytes();
+        self.save_to_writer(&mut buffer)?;
+        tokio::fs::write(filename, buffer).await
     }
 
-    /// Ajoute un nœud en tête de la liste (le plus récemment utilisé).
-    fn add_to_head(&mut self, key: K) {
-        let node = self.map.get_mut(&key).unwrap();
-        node.1.prev = None;
-        node.1.next = self.head.clone();
-
-        if let Some(ref old_head_key) = self.head {
-            let old_head_node = self.map.get_mut(old_head_key).unwrap();
-            old_head_node.1.prev = Some(key.clone());
-        }
+    /// Version asynchrone de `load_from_file` (fonctionnalité `tokio`) : le fichier
+    /// est lu avec `tokio::fs`, puis chargé comme par `load_from_file`, en-tête de
+    /// version compris. Un fichier absent laisse le cache inchangé.
+    #[cfg(feature = "tokio")]
+    pub async fn load_from_file_async(&mut self, filename: &str) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        let content = match tokio::fs::read(filename).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        let mut reader = content.as_slice();
+        read_format_header(&mut reader)?;
+        self.load_from_reader(reader)
+    }
 
-        self.head = Some(key.clone());
+    /// Charge le cache depuis n'importe quelle source implémentant `BufRead`
+    /// (entrée standard, flux réseau, tampon en mémoire...).
+    ///
+    /// Les lignes qui ne respectent pas le format `clé\tvaleur` sont ignorées, de
+    /// même que les lignes vides (ou faites d'espaces) et les commentaires, qui
+    /// commencent par `#` : une clé commençant par `#` ne peut donc pas être
+    /// rechargée. Les clés en double sont traitées selon le [`DuplicatePolicy`] du
+    /// cache.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<String, String> = Cache::new(3);
+    /// cache.load_from_reader("A\tvalue_a\n".as_bytes()).unwrap();
+    /// assert_eq!(cache.get(&"A".to_string()), Some(&"value_a".to_string()));
+    /// ```
+    pub fn load_from_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        self.load_lines(reader, Self::parse_line)
+    }
 
-        if self.tail.is_none() {
-            self.tail = Some(key);
+    /// Insère les entrées lues ligne à ligne par `parse`, en ignorant les lignes
+    /// mal formées et en appliquant le [`DuplicatePolicy`] du cache.
+    fn load_lines<R, P>(&mut self, reader: R, parse: P) -> io::Result<()>
+    where
+        R: BufRead,
+        P: Fn(&str) -> Option<(K, V)>,
+    {
+        for (key, value) in self.read_lines(reader, parse)? {
+            self.put(key, value);
         }
+        Ok(())
     }
 
-    /// Déplace un nœud en tête de la liste (le marque comme le plus récemment utilisé).
-    fn move_to_head(&mut self, key: &K) {
-        self.remove_node(key);
-        self.add_to_head(key.clone());
+    /// Lit les entrées ligne à ligne avec `parse`, sans les insérer, en ignorant les
+    /// lignes mal formées et en appliquant le [`DuplicatePolicy`] du cache.
+    fn read_lines<R, P>(&self, reader: R, parse: P) -> io::Result<Vec<(K, V)>>
+    where
+        R: BufRead,
+        P: Fn(&str) -> Option<(K, V)>,
+    {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if Self::is_annotation(&line) {
+                continue;
+            }
+            let Some((key, value)) = parse(&line) else {
+                continue;
+            };
+            if !seen.insert(key.clone()) {
+                match self.duplicate_policy {
+                    DuplicatePolicy::LastWins => {}
+                    DuplicatePolicy::FirstWins => continue,
+                    DuplicatePolicy::Error => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "clé en double dans le fichier de cache",
+                        ));
+                    }
+                }
+            }
+            entries.push((key, value));
+        }
+        Ok(entries)
     }
 
-    /// Supprime le nœud le moins récemment utilisé (en queue de liste).
-    fn remove_tail(&mut self) {
-        if let Some(tail_key) = self.tail.clone() {
-            self.remove_node(&tail_key);
-            self.map.remove(&tail_key);
+    /// Charge uniquement les `k` dernières lignes d'un fichier, c'est-à-dire les `k`
+    /// entrées les plus récemment utilisées (le fichier étant écrit de la moins
+    /// récemment utilisée à la plus récemment utilisée par `save_to_file`).
+    ///
+    /// Utile pour démarrer rapidement à partir d'un fichier volumineux.
+    pub fn load_recent_from_file(&mut self, filename: &str, k: usize) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        if !Path::new(filename).exists() {
+            return Ok(());
+        }
+        let reader = open_cache_file(filename)?;
+        let mut recent = VecDeque::with_capacity(k);
+        for line in reader.lines() {
+            let line = line?;
+            if k == 0 || Self::is_annotation(&line) {
+                continue;
+            }
+            if recent.len() == k {
+                recent.pop_front();
+            }
+            recent.push_back(line);
+        }
+        for line in recent {
+            if let Some((key, value)) = Self::parse_line(&line) {
+                self.put(key, value);
+            }
         }
+        Ok(())
     }
 
-}
-
-impl<K: Eq + Hash + Clone, V> LRUCache<K, V> for Cache<K, V> {
-    /// Insère une paire clé-valeur dans le cache.
+    /// Charge successivement plusieurs fichiers de cache dans ce cache, par exemple
+    /// quand la persistance est répartie entre plusieurs fichiers.
+    ///
+    /// À l'intérieur d'un fichier, les clés en double suivent le [`DuplicatePolicy`]
+    /// du cache ; une clé déjà présente (chargée depuis un fichier précédent ou
+    /// insérée auparavant) suit `strategy`. Les fichiers absents sont ignorés.
     ///
     /// # Exemple
     ///
     /// ```
-    /// use cachelru::cache::{Cache, LRUCache};
+    /// use cachelru::cache::{Cache, LRUCache, MergeStrategy};
     ///
-    /// let mut cache: Cache<&str, i32> = Cache::new(3);
-    /// cache.put("A", 1);
+    /// std::fs::write("fragment_1.txt", "A\t1\n").unwrap();
+    /// std::fs::write("fragment_2.txt", "A\t2\nB\t3\n").unwrap();
+    /// let mut cache: Cache<String, u32> = Cache::new(10);
+    /// cache
+    ///     .load_from_files(&["fragment_1.txt", "fragment_2.txt"], MergeStrategy::KeepExisting)
+    ///     .unwrap();
+    /// assert_eq!(cache.get(&"A".to_string()), Some(&1));
+    /// # std::fs::remove_file("fragment_1.txt").unwrap();
+    /// # std::fs::remove_file("fragment_2.txt").unwrap();
     /// ```
-    fn put(&mut self, key: K, value: V) {
-        if self.map.contains_key(&key) {
-            self.remove_node(&key);
-        } else {
-            if self.map.len() == self.capacity {
-                self.remove_tail();
+    pub fn load_from_files(&mut self, filenames: &[&str], strategy: MergeStrategy) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        for filename in filenames {
+            if !Path::new(filename).exists() {
+                continue;
+            }
+            let reader = open_cache_file(filename)?;
+            for (key, value) in self.read_lines(reader, Self::parse_line)? {
+                if strategy == MergeStrategy::KeepExisting && self.find_live(&key).is_some() {
+                    continue;
+                }
+                self.put(key, value);
             }
         }
+        Ok(())
+    }
 
-        self.map.insert(
-            key.clone(),
-            (
-                value,
-                Node {
-                    prev: None,
-                    next: None,
-                },
-            ),
-        );
-        self.add_to_head(key);
+    /// Sauvegarde le cache dans un fichier en indiquant explicitement le rang
+    /// d'utilisation de chaque entrée (voir `save_to_writer_ordered`).
+    pub fn save_to_file_ordered(&self, filename: &str) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+        V: std::fmt::Display,
+    {
+        let file = File::create(filename)?;
+        self.save_to_writer_ordered(file)
     }
 
-    /// Récupère une valeur du cache par sa clé.
+    /// Écrit le contenu du cache au format `rang\tclé\tvaleur`, le rang 0 désignant
+    /// l'entrée la plus récemment utilisée. Relu avec `load_from_reader_ordered`,
+    /// l'ordre d'utilisation est reconstruit d'après les rangs, quel que soit l'ordre
+    /// des lignes dans le fichier.
     ///
     /// # Exemple
     ///
     /// ```
     /// use cachelru::cache::{Cache, LRUCache};
     ///
-    /// let mut cache: Cache<&str, i32> = Cache::new(3);
-    /// cache.put("A", 1);
-    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// let mut cache: Cache<String, String> = Cache::new(3);
+    /// cache.put("A".to_string(), "value_a".to_string());
+    /// cache.put("B".to_string(), "value_b".to_string());
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// cache.save_to_writer_ordered(&mut buffer).unwrap();
+    /// assert_eq!(buffer, b"0\tB\tvalue_b\n1\tA\tvalue_a\n");
     /// ```
-    fn get(&mut self, key: &K) -> Option<&V> {
-        if !self.map.contains_key(key) {
-            return None;
+    pub fn save_to_writer_ordered<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+        V: std::fmt::Display,
+    {
+        for (rank, index) in self.indices_from_head().enumerate() {
+            let node = self.node(index);
+            writeln!(writer, "{}\t{}\t{}", rank, node.key, node.value)?;
         }
-        self.move_to_head(key);
-        Some(&self.map.get(key).unwrap().0)
+        writer.flush()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Charge le cache depuis un fichier écrit par `save_to_file_ordered`.
+    pub fn load_from_file_ordered(&mut self, filename: &str) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        if !Path::new(filename).exists() {
+            return Ok(());
+        }
+        self.load_from_reader_ordered(open_cache_file(filename)?)
+    }
 
-    #[test]
-    fn test_lru_cache() {
-        let mut cache = Cache::new(3); // Taille de 3
-        cache.put("A", String::from("value_a"));
-        cache.put("B", String::from("value_b"));
-        cache.put("C", String::from("value_c"));
-        cache.put("D", String::from("value_d"));
-        // Cache == [B, C, D]
+    /// Charge le cache depuis un flux au format `rang\tclé\tvaleur` : les entrées
+    /// sont triées par rang avant d'être insérées, de la moins récemment utilisée à
+    /// la plus récente. Les lignes mal formées sont ignorées ; pour une clé en double,
+    /// la ligne de plus petit rang l'emporte.
+    pub fn load_from_reader_ordered<R: BufRead>(&mut self, reader: R) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let Some((rank, rest)) = line.split_once('\t') else {
+                continue;
+            };
+            if let (Ok(rank), Some((key, value))) = (rank.parse::<usize>(), Self::parse_line(rest))
+            {
+                entries.push((rank, key, value));
+            }
+        }
+        entries.sort_by_key(|&(rank, _, _)| std::cmp::Reverse(rank));
+        for (_, key, value) in entries {
+            self.put(key, value);
+        }
+        Ok(())
+    }
 
-        let my_value = cache.get(&"A");
-        assert_eq!(my_value, None);
+    /// Sauvegarde le cache dans un fichier en formatant les valeurs avec `fmt`, pour
+    /// les types de valeur qui n'implémentent pas `Display` (données binaires...).
+    ///
+    /// Le format de fichier est celui de `save_to_file` : `fmt` ne doit donc produire
+    /// ni tabulation ni retour à la ligne.
+    pub fn save_to_file_with<F: Fn(&V) -> String>(&self, filename: &str, fmt: F) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+    {
+        let mut writer = io::BufWriter::new(File::create(filename)?);
+        for index in self.indices_from_tail() {
+            let node = self.node(index);
+            writeln!(writer, "{}\t{}", node.key, fmt(&node.value))?;
+        }
+        writer.flush()
+    }
+
+    /// Charge le cache depuis un fichier écrit par `save_to_file_with`, en relisant
+    /// les valeurs avec `parse`. Les lignes pour lesquelles `parse` échoue sont
+    /// ignorées, comme avec `load_from_file`.
+    pub fn load_from_file_with<F: Fn(&str) -> Option<V>>(
+        &mut self,
+        filename: &str,
+        parse: F,
+    ) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+    {
+        if !Path::new(filename).exists() {
+            return Ok(());
+        }
+        let reader = open_cache_file(filename)?;
+        self.load_lines(reader, |line| {
+            let mut parts = line.split('\t');
+            let (k_str, v_str) = (parts.next()?, parts.next()?);
+            Some((k_str.parse().ok()?, parse(v_str)?))
+        })
+    }
+
+    /// Compacte un fichier de cache alimenté par ajouts successifs : seule la
+    /// dernière occurrence de chaque clé est conservée, à la position de cette
+    /// dernière occurrence, et les lignes mal formées sont retirées. L'en-tête de
+    /// version est vérifié comme au chargement, et le fichier compacté commence par
+    /// celui de la version courante. Il est écrit à côté puis renommé, si bien
+    /// qu'une interruption laisse l'ancien fichier intact.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// std::fs::write("cache_a_compacter.txt", "A\t1\nB\t2\nA\t3\n").unwrap();
+    /// Cache::<String, u32>::compact_file("cache_a_compacter.txt").unwrap();
+    /// let compacted = std::fs::read_to_string("cache_a_compacter.txt").unwrap();
+    /// assert_eq!(compacted, "#cachelru v1\nB\t2\nA\t3\n");
+    /// # std::fs::remove_file("cache_a_compacter.txt").unwrap();
+    /// ```
+    pub fn compact_file(filename: &str) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        let reader = open_cache_file(filename)?;
+        let mut lines = Vec::new();
+        let mut last = HashMap::new();
+        for line in reader.lines() {
+            let line = line?;
+            if let Some((key, _)) = Self::parse_line(&line) {
+                last.insert(key, lines.len());
+                lines.push(line);
+            }
+        }
+        let mut kept: Vec<usize> = last.into_values().collect();
+        kept.sort_unstable();
+
+        let temporary = format!("{}.tmp", filename);
+        let mut writer = io::BufWriter::new(File::create(&temporary)?);
+        writeln!(writer, "{}{}", FORMAT_HEADER, FORMAT_VERSION)?;
+        for position in kept {
+            writeln!(writer, "{}", lines[position])?;
+        }
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&temporary, filename)
+    }
+
+    /// Ajoute à la fin de `filename` les entrées modifiées depuis le point de
+    /// contrôle précédent (toutes lors du premier), au format `clé\tvaleur` et de la
+    /// moins récemment utilisée à la plus récemment utilisée. Le fichier grandit
+    /// ainsi par incréments au lieu d'être réécrit ; `compact_file` le resserre.
+    ///
+    /// Les suppressions et les évictions ne sont pas enregistrées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<String, u32> = Cache::new(3);
+    /// cache.put("A".to_string(), 1);
+    /// cache.checkpoint_to_file("cache_points.txt").unwrap();
+    /// cache.put("B".to_string(), 2);
+    /// cache.checkpoint_to_file("cache_points.txt").unwrap();
+    /// let content = std::fs::read_to_string("cache_points.txt").unwrap();
+    /// assert_eq!(content, "A\t1\nB\t2\n");
+    /// # std::fs::remove_file("cache_points.txt").unwrap();
+    /// ```
+    pub fn checkpoint_to_file(&mut self, filename: &str) -> io::Result<()>
+    where
+        K: std::fmt::Display,
+        V: std::fmt::Display,
+    {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(filename)?;
+        let mut writer = io::BufWriter::new(file);
+        for index in self.indices_from_tail() {
+            let node = self.node(index);
+            if node.modified > self.checkpointed {
+                writeln!(writer, "{}\t{}", node.key, node.value)?;
+            }
+        }
+        writer.flush()?;
+        self.checkpointed = self.generation;
+        Ok(())
+    }
+
+    /// Recharge un fichier écrit par `checkpoint_to_file` en rejouant les incréments
+    /// dans l'ordre : la dernière valeur écrite pour chaque clé l'emporte, quel que
+    /// soit le [`DuplicatePolicy`] du cache. Les lignes mal formées sont ignorées et
+    /// un fichier absent laisse le cache inchangé.
+    pub fn load_checkpoints_from_file(&mut self, filename: &str) -> io::Result<()>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        if !Path::new(filename).exists() {
+            return Ok(());
+        }
+        let reader = open_cache_file(filename)?;
+        for line in reader.lines() {
+            if let Some((key, value)) = Self::parse_line(&line?) {
+                self.put(key, value);
+            }
+        }
+        // Le contenu rechargé est déjà dans le fichier
+        self.checkpointed = self.generation;
+        Ok(())
+    }
+
+    /// Indique si une ligne d'un fichier de cache est vide ou un commentaire (`#`),
+    /// et ne porte donc pas d'entrée.
+    fn is_annotation(line: &str) -> bool {
+        let line = line.trim();
+        line.is_empty() || line.starts_with('#')
+    }
+
+    /// Découpe une ligne `clé\tvaleur` ; renvoie `None` si elle est mal formée.
+    fn parse_line(line: &str) -> Option<(K, V)>
+    where
+        K: std::str::FromStr,
+        V: std::str::FromStr,
+    {
+        let mut parts = line.split('\t');
+        let (k_str, v_str) = (parts.next()?, parts.next()?);
+        Some((k_str.parse().ok()?, v_str.parse().ok()?))
+    }
+
+    /// Produit un nouveau cache de même capacité, avec les mêmes clés et le même
+    /// ordre d'utilisation, dont chaque valeur est transformée par `f`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// let mut strings: Cache<&str, String> = cache.map_values(|v| v.to_string());
+    /// assert_eq!(strings.get(&"A"), Some(&"1".to_string()));
+    /// ```
+    pub fn map_values<W, F: FnMut(&V) -> W>(&self, mut f: F) -> Cache<K, W> {
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| {
+                slot.as_ref().map(|node| Node {
+                    key: node.key.clone(),
+                    value: f(&node.value),
+                    prev: node.prev,
+                    next: node.next,
+                    referenced: node.referenced,
+                    // Le pondérateur porte sur `V` : il n'est pas conservé
+                    weight: 0,
+                    expires_at: node.expires_at,
+                    frequency: node.frequency,
+                    hits: node.hits,
+                    modified: node.modified,
+                    pinned: node.pinned,
+                    last_access: node.last_access,
+                    access_count: node.access_count,
+                    priority: node.priority,
+                })
+            })
+            .collect();
+        Cache {
+            capacity: self.capacity,
+            policy: self.policy,
+            lfu_tiebreak: self.lfu_tiebreak,
+            duplicate_policy: self.duplicate_policy,
+            map: self.map.clone(),
+            slots,
+            free: self.free.clone(),
+            head: self.head,
+            tail: self.tail,
+            weigher: None,
+            max_weight: usize::MAX,
+            total_weight: 0,
+            clock: self.clock.clone(),
+            negative: self.negative.clone(),
+            negative_ttl: self.negative_ttl,
+            promote_threshold: self.promote_threshold,
+            accesses: self.accesses,
+            stats: self.stats,
+            reset_stats_on_resize: self.reset_stats_on_resize,
+            idle_check: self.idle_check,
+            timed_accesses: self.timed_accesses,
+            generation: self.generation,
+            checkpointed: self.checkpointed,
+            flushed: self.flushed,
+            // Le nouveau cache a son propre flux d'événements
+            subscribers: Vec::new(),
+            // Le prédicat porte sur `V` : il n'est pas conservé
+            pin_predicate: None,
+            rejected: None,
+            autogrow: self.autogrow,
+            bloom: self.bloom.clone(),
+            prioritized: self.prioritized,
+            watermarks: self.watermarks,
+            // Les valeurs évincées sont d'un autre type : pas de canal
+            evictions: None,
+            #[cfg(feature = "logging")]
+            key_fmt: self.key_fmt,
+        }
+    }
+
+    /// Insère une paire clé-valeur et renvoie à la fois l'entrée évincée (s'il a
+    /// fallu faire de la place) et une référence vers la valeur insérée.
+    ///
+    /// Un cache limité seulement en nombre d'entrées en évince au plus une. Avec
+    /// une limite de poids ou des seuils d'éviction (`set_watermarks`), une même
+    /// insertion peut en évincer plusieurs : la moins récemment utilisée est
+    /// renvoyée, et les suivantes passent par le canal d'éviction
+    /// (`eviction_channel`), comme pour `put`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(1);
+    /// cache.put("A", 1);
+    /// let (evicted, value) = cache.insert_reporting("B", 2);
+    /// assert_eq!(*value, 2);
+    /// assert_eq!(evicted, Some(("A", 1)));
+    /// ```
+    pub fn insert_reporting(&mut self, key: K, value: V) -> (Option<(K, V)>, &V) {
+        let mut evicted = None;
+        let mut sink = self.eviction_sink();
+        let index = self.insert_entry(key, value, |k, v| match evicted {
+            None => evicted = Some((k, v)),
+            Some(_) => sink(k, v),
+        });
+        (evicted, self.stored_value(index))
+    }
+
+    /// Insère une paire clé-valeur, évictions comprises, et renvoie une référence
+    /// vers la valeur stockée, sans seconde recherche.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, String> = Cache::new(3);
+    /// let value = cache.put_ref("A", "valeur".to_string());
+    /// assert_eq!(value, "valeur");
+    /// ```
+    pub fn put_ref(&mut self, key: K, value: V) -> &V {
+        let index = self.insert_entry(key, value, self.eviction_sink());
+        self.stored_value(index)
+    }
+
+    /// Supprime une entrée du cache et renvoie sa valeur.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.remove(&"A"), Some(1));
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = self.remove_entry(key).map(|(_, value)| value);
+        self.debug_validate();
+        removed
+    }
+
+    /// Retire une entrée du cache et renvoie la valeur possédée ; synonyme de `remove`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, String> = Cache::new(3);
+    /// cache.put("A", "value_a".to_string());
+    /// assert_eq!(cache.take(&"A"), Some("value_a".to_string()));
+    /// assert_eq!(cache.take(&"A"), None);
+    /// ```
+    pub fn take(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    /// Renvoie la somme des poids des entrées présentes (0 sans pondérateur).
+    pub fn total_weight(&self) -> usize {
+        self.total_weight
+    }
+
+    /// Renvoie les limites du cache : la capacité en nombre d'entrées et, si le
+    /// cache est pondéré, le poids maximal. L'éviction se déclenche dès que l'une
+    /// ou l'autre est atteinte.
+    pub fn limits(&self) -> (usize, Option<usize>) {
+        (self.capacity, self.max_weight())
+    }
+
+    /// Renvoie le poids maximal autorisé, si le cache est pondéré.
+    pub fn max_weight(&self) -> Option<usize> {
+        self.weigher.as_ref().map(|_| self.max_weight)
+    }
+
+    /// Insère les entrées triées par clé, pour un ordre d'utilisation reproductible
+    /// quel que soit l'ordre d'itération de la source (un `HashMap` par exemple).
+    ///
+    /// La plus petite clé est insérée en premier : parmi ces entrées, c'est donc
+    /// elle qui sera évincée en premier.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let entries: HashMap<&str, i32> = HashMap::from([("B", 2), ("A", 1)]);
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put_sorted(entries);
+    /// cache.put("C", 3); // Évince "A"
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn put_sorted<I>(&mut self, entries: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Ord,
+    {
+        let mut entries: Vec<(K, V)> = entries.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (key, value) in entries {
+            self.put(key, value);
+        }
+    }
+
+    /// Remplace l'horloge utilisée pour les durées de vie (utile pour les tests).
+    pub fn set_clock<F>(&mut self, clock: F)
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        self.clock = Clock(Arc::new(clock));
+    }
+
+    /// Insère une paire clé-valeur qui expire au bout de `ttl`.
+    ///
+    /// Une entrée expirée n'est plus renvoyée par `get` et est supprimée lors de
+    /// cet accès ; d'ici là, elle occupe toujours une place dans le cache.
+    ///
+    /// Avec une durée nulle, la valeur est considérée comme déjà expirée : l'appel
+    /// est sans effet. Elle n'évince aucune entrée valide, et une entrée existante
+    /// pour cette clé est conservée telle quelle. À l'inverse, une durée trop
+    /// grande pour l'horloge (`Duration::MAX`...) insère une entrée sans échéance.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put_with_ttl("A", 1, Duration::from_secs(60));
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// ```
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+        if ttl.is_zero() {
+            return;
+        }
+        let expires_at = (self.clock.0)().checked_add(ttl);
+        if let Some(index) = self.insert_entry(key, value, self.eviction_sink()) {
+            self.node_mut(index).expires_at = expires_at;
+        }
+    }
+
+    /// Insère une paire clé-valeur avec une priorité : l'éviction choisit toujours
+    /// parmi les entrées de plus faible priorité, selon la stratégie du cache
+    /// (l'entrée la moins récemment utilisée en mode LRU). Les entrées insérées par
+    /// `put` ont la priorité 0 ; une mise à jour par `put` conserve la priorité.
+    ///
+    /// Dès qu'une priorité a été donnée, chaque éviction parcourt tout le cache pour
+    /// trouver la plus faible priorité.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put_with_priority("A", 1, 5);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3); // Évince "B", de priorité plus faible que "A"
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn put_with_priority(&mut self, key: K, value: V, priority: u8) {
+        if let Some(index) = self.insert_entry(key, value, self.eviction_sink()) {
+            self.node_mut(index).priority = priority;
+            self.prioritized |= priority > 0;
+        }
+        self.debug_validate();
+    }
+
+    /// Renvoie le temps restant avant l'expiration d'une entrée :
+    /// `Some(Duration::ZERO)` si elle est déjà expirée, `None` si la clé est absente
+    /// ou si l'entrée n'a pas d'échéance. L'entrée n'est ni promue ni supprimée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put_with_ttl("A", 1, Duration::from_secs(60));
+    /// assert!(cache.ttl_remaining(&"A").unwrap() <= Duration::from_secs(60));
+    /// assert_eq!(cache.ttl_remaining(&"B"), None);
+    /// ```
+    pub fn ttl_remaining(&self, key: &K) -> Option<Duration> {
+        let expires_at = self.node(*self.map.get(key)?).expires_at?;
+        Some(expires_at.saturating_duration_since((self.clock.0)()))
+    }
+
+    /// Récupère une valeur en repoussant son échéance à `maintenant + ttl`
+    /// (expiration glissante) : une entrée consultée régulièrement n'expire jamais.
+    /// Une échéance que l'horloge ne sait pas représenter (`Duration::MAX`...)
+    /// retire toute échéance à l'entrée.
+    ///
+    /// Renvoie `None` si la clé est absente ou déjà expirée.
+    pub fn get_sliding(&mut self, key: &K, ttl: Duration) -> Option<&V> {
+        let index = self.live_index(key)?;
+        self.promote(index);
+        let expires_at = (self.clock.0)().checked_add(ttl);
+        let node = self.node_mut(index);
+        node.expires_at = expires_at;
+        Some(&node.value)
+    }
+
+    /// Retire et renvoie toutes les entrées expirées selon l'horloge du cache, de la
+    /// plus récemment utilisée à la moins récente, par exemple pour journaliser un
+    /// nettoyage différé. Les entrées valides gardent leur ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put_with_ttl("B", 2, Duration::from_secs(60));
+    /// assert!(cache.drain_expired().is_empty());
+    /// ```
+    pub fn drain_expired(&mut self) -> Vec<(K, V)> {
+        let now = (self.clock.0)();
+        let expired: Vec<usize> = self
+            .indices_from_head()
+            .filter(|&index| {
+                self.node(index)
+                    .expires_at
+                    .is_some_and(|deadline| deadline <= now)
+            })
+            .collect();
+        expired
+            .into_iter()
+            .map(|index| {
+                let node = self.take_index(index);
+                (node.key, node.value)
+            })
+            .collect()
+    }
+
+    /// Supprime toutes les entrées expirées sans les renvoyer, et indique combien
+    /// ont été supprimées.
+    pub fn purge_expired(&mut self) -> usize {
+        self.drain_expired().len()
+    }
+
+    /// Active le cache négatif : une clé pour laquelle le chargeur de
+    /// `get_or_try_insert_with` ne renvoie rien est mémorisée comme absente pendant
+    /// `ttl`, et le chargeur n'est plus appelé pour elle d'ici là. `None` désactive
+    /// le cache négatif et oublie les clés mémorisées.
+    ///
+    /// Les clés absentes ne comptent pas dans la capacité du cache ; elles sont
+    /// oubliées dès qu'une valeur est insérée pour elles.
+    pub fn set_negative_ttl(&mut self, ttl: Option<Duration>) {
+        self.negative_ttl = ttl;
+        if ttl.is_none() {
+            self.negative.clear();
+        }
+    }
+
+    /// Active un filtre de Bloom de `size` compteurs (un octet chacun) tenu à jour à
+    /// chaque insertion, suppression et éviction, ou le désactive avec `None`. Les
+    /// lectures le consultent avant la table : une clé que le filtre sait absente
+    /// est écartée sans recherche, ce qui accélère les défauts de cache.
+    ///
+    /// Le filtre ne donne jamais de faux négatif. Il peut en revanche répondre
+    /// « peut-être présente » pour une clé absente (faux positif) : la lecture
+    /// consulte alors la table comme sans filtre. Chaque clé touchant quatre
+    /// compteurs, prévoir dix compteurs par entrée donne environ 1 % de faux positifs.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(100);
+    /// cache.put("A", 1);
+    /// cache.set_bloom_filter(Some(1000));
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn set_bloom_filter(&mut self, size: Option<usize>) {
+        self.bloom = size.map(|size| {
+            let mut bloom = BloomFilter::new(size);
+            for key in self.map.keys() {
+                bloom.insert(self.map.hasher().hash_one(key));
+            }
+            bloom
+        });
+    }
+
+    /// Indique si la clé est actuellement mémorisée comme absente par le cache négatif.
+    pub fn is_negative_cached(&self, key: &K) -> bool {
+        self.negative
+            .get(key)
+            .is_some_and(|&deadline| deadline > (self.clock.0)())
+    }
+
+    /// Récupère une valeur, ou la charge avec `f` si elle est absente. Si `f` ne
+    /// renvoie rien, `None` est renvoyé et, lorsque le cache négatif est actif (voir
+    /// `set_negative_ttl`), la clé est mémorisée comme absente : les appels suivants
+    /// renvoient `None` sans rappeler le chargeur jusqu'à l'échéance.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.set_negative_ttl(Some(Duration::from_secs(30)));
+    /// assert_eq!(cache.get_or_try_insert_with("A", || None), None);
+    /// assert!(cache.is_negative_cached(&"A"));
+    /// assert_eq!(cache.get_or_try_insert_with("A", || Some(1)), None);
+    /// ```
+    pub fn get_or_try_insert_with<F: FnOnce() -> Option<V>>(&mut self, key: K, f: F) -> Option<&V> {
+        if let Some(index) = self.live_index(&key) {
+            self.promote(index);
+            return Some(&self.node(index).value);
+        }
+        if self.is_negative_cached(&key) {
+            return None;
+        }
+        match f() {
+            Some(value) => {
+                let index = self.insert_loaded(|| key, value);
+                Some(self.stored_value(index))
+            }
+            None => {
+                if let Some(ttl) = self.negative_ttl {
+                    let deadline = (self.clock.0)() + ttl;
+                    self.negative.insert(key, deadline);
+                }
+                None
+            }
+        }
+    }
+
+    /// Active ou désactive la datation de chaque insertion et lecture, sur laquelle
+    /// repose `retain_recent`. Désactivée par défaut, pour que les accès n'interrogent
+    /// pas l'horloge ; à l'activation, les entrées présentes sont datées de l'instant
+    /// courant.
+    pub fn set_track_access_times(&mut self, enabled: bool) {
+        if enabled == self.timed_accesses {
+            return;
+        }
+        self.timed_accesses = enabled;
+        let now = enabled.then(|| (self.clock.0)());
+        for node in self.slots.iter_mut().flatten() {
+            node.last_access = now;
+        }
+    }
+
+    /// Supprime toutes les entrées qui n'ont été ni insérées ni lues depuis
+    /// `within` : contrairement à une durée de vie fixée à l'insertion, chaque accès
+    /// repousse l'échéance. Les lectures qui ne touchent pas l'ordre d'utilisation
+    /// (`peek_mut_if`, `mru_n`...) ne comptent pas comme des accès.
+    ///
+    /// Les accès ne sont datés qu'une fois `set_track_access_times` activé : sans
+    /// cela, l'appel ne fait rien et renvoie `false`. Il renvoie `true` sinon.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.set_track_access_times(true);
+    /// cache.put("A", 1);
+    /// assert!(cache.retain_recent(Duration::from_secs(60)));
+    /// assert_eq!(cache.len(), 1);
+    /// ```
+    pub fn retain_recent(&mut self, within: Duration) -> bool {
+        if !self.timed_accesses {
+            return false;
+        }
+        let Some(cutoff) = (self.clock.0)().checked_sub(within) else {
+            return true;
+        };
+        let idle: Vec<usize> = self
+            .indices_from_head()
+            .filter(|&index| self.node(index).last_access.is_some_and(|at| at < cutoff))
+            .collect();
+        for index in idle {
+            self.take_index(index);
+        }
+        true
+    }
+
+    /// Retire et renvoie toutes les entrées pour lesquelles `pred` est vrai, de la
+    /// plus récemment utilisée à la moins récente. Les entrées restantes gardent
+    /// leur ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.extract_if(|_, v| *v == 2), vec![("B", 2)]);
+    /// ```
+    pub fn extract_if<F: FnMut(&K, &V) -> bool>(&mut self, mut pred: F) -> Vec<(K, V)> {
+        let matching: Vec<usize> = self
+            .indices_from_head()
+            .filter(|&index| {
+                let node = self.node(index);
+                pred(&node.key, &node.value)
+            })
+            .collect();
+        matching
+            .into_iter()
+            .map(|index| {
+                let node = self.take_index(index);
+                (node.key, node.value)
+            })
+            .collect()
+    }
+
+    /// Compte les entrées vérifiant `pred`, sans rien allouer ni modifier l'ordre
+    /// d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 20);
+    /// assert_eq!(cache.count_where(|_, v| *v > 10), 1);
+    /// ```
+    pub fn count_where<F: FnMut(&K, &V) -> bool>(&self, mut pred: F) -> usize {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|node| pred(&node.key, &node.value))
+            .count()
+    }
+
+    /// Choisit la victime parmi les entrées de même fréquence minimale en mode LFU.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, EvictionPolicy, LfuTiebreak};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::with_policy(3, EvictionPolicy::Lfu);
+    /// cache.set_lfu_tiebreak(LfuTiebreak::NewestFirst);
+    /// ```
+    pub fn set_lfu_tiebreak(&mut self, tiebreak: LfuTiebreak) {
+        self.lfu_tiebreak = tiebreak;
+    }
+
+    /// Change la stratégie d'éviction d'un cache en service, sans perdre d'entrée.
+    ///
+    /// L'ordre de la liste est conservé et sert de point de départ à la nouvelle
+    /// stratégie, mais les informations propres à l'ancienne sont perdues :
+    /// - les fréquences LFU repartent toutes à 1, l'ordre de la liste départageant
+    ///   ensuite les entrées ;
+    /// - les bits de référence CLOCK sont effacés ;
+    /// - depuis CLOCK, l'ordre de la liste reflète l'ordre d'insertion (et les
+    ///   secondes chances accordées) plutôt que l'ordre exact d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, EvictionPolicy, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.convert_policy(EvictionPolicy::Lfu);
+    /// cache.get(&"A");
+    /// cache.get(&"A");
+    /// cache.put("C", 3); // Évince "B", le moins fréquemment utilisé
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn convert_policy(&mut self, new: EvictionPolicy) {
+        for node in self.slots.iter_mut().flatten() {
+            node.frequency = 1;
+            node.referenced = false;
+            node.hits = 0;
+        }
+        self.policy = new;
+    }
+
+    /// Renvoie les succès et échecs des recherches (voir [`CacheStats`]).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, CacheStats, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.get(&"A");
+    /// cache.get(&"B");
+    /// assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Remet les statistiques de consultation à zéro.
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    /// Indique si un changement effectif de capacité par `resize` (ou
+    /// `with_temporary_capacity`, croissance automatique...) remet les
+    /// statistiques à zéro, pour mesurer le taux de succès de chaque
+    /// configuration séparément. Désactivé par défaut : les statistiques couvrent
+    /// alors toute la vie du cache.
+    pub fn set_reset_stats_on_resize(&mut self, reset: bool) {
+        self.reset_stats_on_resize = reset;
+    }
+
+    /// Change la capacité du cache. Un changement effectif est signalé aux abonnés
+    /// par [`CacheEvent::Resize`], et remet les statistiques à zéro si
+    /// `set_reset_stats_on_resize` l'a demandé (elles sont conservées par défaut).
+    ///
+    /// Si la capacité diminue sous le nombre d'entrées, `len - capacity` entrées
+    /// sont évincées une à une selon la stratégie du cache : en mode LRU, ce sont
+    /// exactement les moins récemment utilisées, retirées en partant de la queue,
+    /// et les entrées conservées gardent leur ordre. Les entrées épinglées et les
+    /// priorités de `put_with_priority` sont respectées comme pour toute éviction.
+    /// Si la capacité augmente, aucune entrée n'est déplacée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.resize(1); // Évince "A"
+    /// assert_eq!(cache.get(&"A"), None);
+    /// assert_eq!(cache.capacity(), 1);
+    /// ```
+    pub fn resize(&mut self, capacity: usize) {
+        let old = self.capacity;
+        self.capacity = capacity;
+        let mut sink = self.eviction_sink();
+        while self.map.len() > capacity {
+            match self.evict() {
+                Some((key, value)) => sink(key, value),
+                None => break,
+            }
+        }
+        if old != capacity {
+            if self.reset_stats_on_resize {
+                self.stats = CacheStats::default();
+            }
+            self.emit(CacheEvent::Resize { old, new: capacity });
+        }
+        self.debug_validate();
+    }
+
+    /// Active la croissance automatique : lorsque `put` trouve le cache plein à
+    /// chacune d'une série d'insertions aussi longue que la capacité, et qu'au moins
+    /// la moitié de ces insertions portaient sur une nouvelle clé (un défaut de
+    /// cache suivi d'un chargement, typiquement), la capacité est multipliée par
+    /// `growth_factor`, sans dépasser `max_capacity`. Chaque croissance grandit d'au
+    /// moins une entrée et passe par `resize`, qui la signale aux abonnés.
+    ///
+    /// Seul `put` alimente l'observation ; une insertion trouvant de la place remet
+    /// la série à zéro.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, u32> = Cache::new(2);
+    /// cache.set_autogrow(8, 2.0);
+    /// for i in 0..4 {
+    ///     cache.put(i, i);
+    /// }
+    /// assert_eq!(cache.capacity(), 4);
+    /// ```
+    pub fn set_autogrow(&mut self, max_capacity: usize, growth_factor: f64) {
+        self.autogrow = Some(Autogrow {
+            max_capacity,
+            growth_factor,
+            puts: 0,
+            misses: 0,
+        });
+    }
+
+    /// Active l'éviction par lots : lorsqu'une nouvelle entrée trouve le cache à
+    /// `high` entrées, les entrées sont évincées selon la stratégie du cache jusqu'à
+    /// n'en laisser que `low`, puis la nouvelle entrée est ajoutée. Les insertions
+    /// suivantes trouvent ainsi de la place sans éviction jusqu'au prochain passage
+    /// de `high`. La capacité reste une borne : au-delà, l'éviction se fait une
+    /// entrée à la fois.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `low` n'est pas strictement inférieur à `high`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, u32> = Cache::new(10);
+    /// cache.set_watermarks(8, 4);
+    /// for i in 0..9 {
+    ///     cache.put(i, i);
+    /// }
+    /// assert_eq!(cache.len(), 5);
+    /// ```
+    pub fn set_watermarks(&mut self, high: usize, low: usize) {
+        assert!(
+            low < high,
+            "le seuil bas ({low}) doit être inférieur au seuil haut ({high})"
+        );
+        self.watermarks = Some((high, low));
+    }
+
+    /// Porte temporairement la capacité à `temp` le temps d'exécuter `f`, par
+    /// exemple pour absorber une rafale connue d'insertions, puis rétablit la
+    /// capacité d'origine en évinçant selon la stratégie du cache ce qui dépasse.
+    /// Les abonnés reçoivent les deux `CacheEvent::Resize`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, u32> = Cache::new(2);
+    /// let len = cache.with_temporary_capacity(10, |cache| {
+    ///     for i in 0..5 {
+    ///         cache.put(i, i);
+    ///     }
+    ///     cache.len()
+    /// });
+    /// assert_eq!(len, 5);
+    /// assert_eq!((cache.capacity(), cache.len()), (2, 2));
+    /// ```
+    pub fn with_temporary_capacity<R>(&mut self, temp: usize, f: impl FnOnce(&mut Self) -> R) -> R {
+        let original = self.capacity;
+        self.resize(temp);
+        let result = f(self);
+        self.resize(original);
+        result
+    }
+
+    /// S'abonne aux changements structurels du cache (changement de capacité,
+    /// vidage). Les événements sont envoyés dans l'ordre où ils se produisent ; un
+    /// abonné est oublié dès que son `Receiver` est abandonné.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, CacheEvent};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// let events = cache.subscribe();
+    /// cache.clear();
+    /// assert_eq!(events.try_recv(), Ok(CacheEvent::Clear));
+    /// ```
+    pub fn subscribe(&mut self) -> Receiver<CacheEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Ouvre un canal recevant, dans l'ordre, chaque entrée évincée pour faire de
+    /// la place (insertion dans un cache plein, poids maximal, seuils de
+    /// `set_watermarks`, `resize`) : une file d'écriture différée, qui reçoit les
+    /// clés et valeurs elles-mêmes. Les suppressions explicites et les entrées
+    /// expirées n'y passent pas, pas plus que l'éviction déjà rendue à l'appelant
+    /// par `insert_reporting`. Un nouvel appel remplace le canal
+    /// précédent.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(1);
+    /// let evictions = cache.eviction_channel();
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(evictions.try_recv(), Ok(("A", 1)));
+    /// ```
+    pub fn eviction_channel(&mut self) -> Receiver<(K, V)> {
+        let (sender, receiver) = mpsc::channel();
+        self.evictions = Some(sender);
+        receiver
+    }
+
+    /// Fait figurer les clés, formatées avec `Debug`, dans les traces émises avec la
+    /// fonctionnalité `logging` : `trace` à chaque éviction, `debug` à chaque
+    /// recherche infructueuse. Sans cet appel, les traces sont émises sans la clé.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(1);
+    /// cache.log_keys();
+    /// cache.put("A", 1);
+    /// cache.put("B", 2); // Trace « entrée évincée : "A" »
+    /// ```
+    #[cfg(feature = "logging")]
+    pub fn log_keys(&mut self)
+    where
+        K: fmt::Debug,
+    {
+        self.key_fmt = Some(<K as fmt::Debug>::fmt);
+    }
+
+    /// En mode LRU, ne promeut une entrée en tête qu'au `threshold`-ième accès
+    /// (lecture ou mise à jour) depuis sa dernière promotion, au lieu de la déplacer
+    /// à chaque accès. Les lectures fréquentes modifient ainsi beaucoup moins la
+    /// liste, au prix d'un ordre LRU approché : une entrée lue moins de `threshold`
+    /// fois depuis sa dernière promotion peut être évincée bien qu'utilisée récemment.
+    ///
+    /// Le seuil par défaut vaut 1 (LRU exact) ; un seuil nul est traité comme 1.
+    /// Les autres stratégies d'éviction ne sont pas concernées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.set_promote_threshold(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.get(&"A"); // Premier accès : pas de promotion
+    /// cache.put("C", 3); // Évince "A"
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn set_promote_threshold(&mut self, threshold: usize) {
+        self.promote_threshold = threshold.max(1);
+    }
+
+    /// Choisit le traitement des clés en double lors des chargements.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, DuplicatePolicy};
+    ///
+    /// let mut cache: Cache<String, String> = Cache::new(3);
+    /// cache.set_duplicate_policy(DuplicatePolicy::Error);
+    /// assert!(cache.load_from_reader("A\t1\nA\t2\n".as_bytes()).is_err());
+    /// ```
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Protège de l'éviction les entrées pour lesquelles `pred` renvoie vrai :
+    /// l'éviction passe ces entrées et choisit la victime suivante selon la
+    /// stratégie du cache. Les suppressions explicites et les expirations ne sont
+    /// pas concernées.
+    ///
+    /// Si toutes les entrées sont épinglées, aucune ne peut être évincée : `put`
+    /// insère alors tout de même la nouvelle entrée et le cache dépasse sa capacité
+    /// (ou son budget de poids), tandis que `put_no_evict` la refuse.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.set_pin_predicate(Box::new(|key, _| *key == "A"));
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3); // Évince "B" plutôt que "A"
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn set_pin_predicate(&mut self, pred: Box<PinFn<K, V>>) {
+        self.pin_predicate = Some(PinPredicate(pred));
+    }
+
+    /// Épingle une entrée présente (et non expirée) : elle n'est plus jamais
+    /// évincée, jusqu'à `unpin`, mais peut toujours être supprimée ou expirer.
+    /// Renvoie `false` si la clé est absente.
+    ///
+    /// Comme avec `set_pin_predicate`, un cache dont toutes les entrées sont
+    /// épinglées dépasse sa capacité lors des insertions suivantes.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// assert!(cache.pin(&"A"));
+    /// cache.put("B", 2);
+    /// cache.put("C", 3); // Évince "B" plutôt que "A"
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// assert_eq!(cache.pinned_count(), 1);
+    /// ```
+    pub fn pin(&mut self, key: &K) -> bool {
+        self.set_pinned(key, true)
+    }
+
+    /// Désépingle une entrée présente, qui redevient évinçable. Renvoie `false` si
+    /// la clé est absente.
+    pub fn unpin(&mut self, key: &K) -> bool {
+        self.set_pinned(key, false)
+    }
+
+    /// Renvoie le nombre d'entrées épinglées avec `pin`.
+    pub fn pinned_count(&self) -> usize {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|node| node.pinned)
+            .count()
+    }
+
+    fn set_pinned(&mut self, key: &K, pinned: bool) -> bool {
+        match self.find_live(key) {
+            Some(index) => {
+                self.node_mut(index).pinned = pinned;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remplace la valeur d'une entrée sans toucher à sa position dans l'ordre
+    /// d'utilisation : ni promotion, ni rétrogradation, les liens `prev`/`next`
+    /// restent intacts.
+    ///
+    /// Renvoie l'ancienne valeur, ou `Err` avec la nouvelle valeur si la clé est
+    /// absente (ou expirée). L'échéance éventuelle de l'entrée est conservée. Aucune
+    /// éviction n'a lieu : si le nouveau poids dépasse le budget, il sera rétabli à
+    /// la prochaine insertion.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.update_in_place(&"A", 10), Ok(1));
+    /// assert_eq!(cache.update_in_place(&"B", 2), Err(2));
+    /// ```
+    pub fn update_in_place(&mut self, key: &K, value: V) -> Result<V, V> {
+        let Some(index) = self.find_live(key) else {
+            return Err(value);
+        };
+        let weight = self.weigh(key, &value);
+        self.mark_modified(index);
+        let node = self.slots[index].as_mut().unwrap();
+        self.total_weight = self.total_weight - node.weight + weight;
+        node.weight = weight;
+        Ok(std::mem::replace(&mut node.value, value))
+    }
+
+    /// Applique `f` à la valeur d'une entrée présente (et non expirée), sans la
+    /// promouvoir, et indique si `f` a été appelée.
+    ///
+    /// Le poids de l'entrée est recalculé après `f`. Comme pour `update_in_place`,
+    /// aucune éviction n'a lieu : si le nouveau poids dépasse le budget, il sera
+    /// rétabli à la prochaine insertion.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert!(cache.peek_mut_if(&"A", |v| *v += 1));
+    /// assert!(!cache.peek_mut_if(&"B", |v| *v += 1));
+    /// assert_eq!(cache.get(&"A"), Some(&2));
+    /// ```
+    pub fn peek_mut_if<F: FnOnce(&mut V)>(&mut self, key: &K, f: F) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        f(&mut self.node_mut(index).value);
+        self.mark_modified(index);
+        let weight = self.weigh(key, &self.node(index).value);
+        let old_weight = std::mem::replace(&mut self.node_mut(index).weight, weight);
+        self.total_weight = self.total_weight - old_weight + weight;
+        true
+    }
+
+    /// Renvoie des références modifiables vers les valeurs de deux clés distinctes,
+    /// ou `None` si l'une est absente (ou expirée) ou si les deux clés sont égales.
+    ///
+    /// Aucune des deux entrées n'est promue, et leur poids n'est pas recalculé
+    /// après modification.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// let (a, b) = cache.get_disjoint_mut(&"A", &"B").unwrap();
+    /// std::mem::swap(a, b);
+    /// assert_eq!(cache.get(&"A"), Some(&2));
+    /// ```
+    pub fn get_disjoint_mut(&mut self, k1: &K, k2: &K) -> Option<(&mut V, &mut V)> {
+        let [a, b] = self.get_many_mut([k1, k2])?;
+        Some((a, b))
+    }
+
+    /// Généralise `get_disjoint_mut` à `N` clés : renvoie des références
+    /// modifiables vers les `N` valeurs si toutes les clés sont présentes (et non
+    /// expirées) et distinctes deux à deux, `None` sinon.
+    ///
+    /// Aucune entrée n'est promue, et leur poids n'est pas recalculé après
+    /// modification.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// for value in cache.get_many_mut([&"A", &"B", &"C"]).unwrap() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(cache.get(&"C"), Some(&30));
+    /// ```
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut V; N]> {
+        let mut indices = [0; N];
+        for (index, key) in indices.iter_mut().zip(keys) {
+            *index = self.find_live(key)?;
+        }
+        if (1..N).any(|i| indices[..i].contains(&indices[i])) {
+            return None;
+        }
+        // Toutes les valeurs peuvent être modifiées : elles sont marquées d'avance
+        for &index in &indices {
+            self.mark_modified(index);
+        }
+        let slots = self.slots.get_disjoint_mut(indices).ok()?;
+        Some(slots.map(|slot| &mut slot.as_mut().unwrap().value))
+    }
+
+    /// Échange les valeurs de deux entrées sans toucher à l'ordre d'utilisation, et
+    /// renvoie `true` si les deux clés sont présentes (et non expirées), `false`
+    /// sinon. Les poids sont recalculés sans éviction, comme pour `peek_mut_if`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert!(cache.swap_values(&"A", &"B"));
+    /// assert!(!cache.swap_values(&"A", &"C"));
+    /// assert_eq!(cache.get(&"A"), Some(&2));
+    /// ```
+    pub fn swap_values(&mut self, k1: &K, k2: &K) -> bool {
+        if k1 == k2 {
+            return self.find_live(k1).is_some();
+        }
+        let Some([a, b]) = self.get_many_mut([k1, k2]) else {
+            return false;
+        };
+        std::mem::swap(a, b);
+        for key in [k1, k2] {
+            let index = self.map[key];
+            let weight = self.weigh(key, &self.node(index).value);
+            let old_weight = std::mem::replace(&mut self.node_mut(index).weight, weight);
+            self.total_weight = self.total_weight - old_weight + weight;
+        }
+        true
+    }
+
+    /// Renvoie le nombre d'entrées présentes dans le cache.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Renvoie la capacité du cache, en nombre d'entrées.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Estime le nombre d'évictions que provoquerait l'insertion de
+    /// `incoming_new_keys` clés toutes absentes du cache, par exemple pour décider
+    /// de vider le cache vers un stockage avant un chargement massif. Au-delà de la
+    /// capacité, les nouvelles entrées s'évincent entre elles et sont comptées
+    /// aussi. L'estimation ne tient compte que de la capacité en entrées, pas du
+    /// poids maximal, des entrées épinglées ni des seuils de `set_watermarks`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.evictions_for(1), 0);
+    /// assert_eq!(cache.evictions_for(3), 2);
+    /// ```
+    pub fn evictions_for(&self, incoming_new_keys: usize) -> usize {
+        self.len()
+            .saturating_add(incoming_new_keys)
+            .saturating_sub(self.capacity)
+    }
+
+    /// Renvoie le taux de remplissage du cache, entre 0.0 et 1.0 (0.0 pour une
+    /// capacité nulle).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(4);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.utilization(), 0.25);
+    /// ```
+    pub fn utilization(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        self.len() as f64 / self.capacity as f64
+    }
+
+    /// Réserve de la place pour au moins `additional` entrées de plus, dans la table
+    /// comme dans les emplacements de la liste. Contrairement à une réservation
+    /// implicite, un échec d'allocation (ou une taille impossible à représenter) est
+    /// renvoyé comme une erreur au lieu d'interrompre le programme ; le cache reste
+    /// alors utilisable.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(1000);
+    /// assert!(cache.try_reserve(100).is_ok());
+    /// assert!(cache.try_reserve(usize::MAX).is_err());
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.map.try_reserve(additional)?;
+        self.slots
+            .try_reserve(additional.saturating_sub(self.free.len()))
+    }
+
+    /// Estime grossièrement l'occupation mémoire du cache sur le tas, en octets :
+    /// la place réservée par la table et les emplacements de la liste (d'après leur
+    /// capacité), plus ce que `sizer` attribue à chaque paire clé-valeur (chaînes,
+    /// vecteurs, etc. détenus par la clé ou la valeur).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, String> = Cache::new(10);
+    /// let empty = cache.estimated_heap_size(|_, v: &String| v.capacity());
+    /// cache.put(1, "abc".to_string());
+    /// assert!(cache.estimated_heap_size(|_, v: &String| v.capacity()) > empty);
+    /// ```
+    pub fn estimated_heap_size<F: Fn(&K, &V) -> usize>(&self, sizer: F) -> usize {
+        // Chaque case de la table contient une clé, un indice et un octet de contrôle
+        let map = self.map.capacity() * (std::mem::size_of::<(K, usize)>() + 1);
+        let slots = self.slots.capacity() * std::mem::size_of::<Option<Node<K, V>>>();
+        let free = self.free.capacity() * std::mem::size_of::<usize>();
+        let entries: usize = self
+            .slots
+            .iter()
+            .flatten()
+            .map(|node| sizer(&node.key, &node.value))
+            .sum();
+        map + slots + free + entries
+    }
+
+    /// Renvoie jusqu'à `n` entrées, de la plus récemment utilisée à la moins
+    /// récente, sans modifier l'ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.mru_n(1), vec![(&"B", &2)]);
+    /// ```
+    pub fn mru_n(&self, n: usize) -> Vec<(&K, &V)> {
+        self.indices_from_head()
+            .take(n)
+            .map(|index| self.entry_at(index))
+            .collect()
+    }
+
+    /// Renvoie jusqu'à `n` entrées, de la moins récemment utilisée à la plus
+    /// récente, sans modifier l'ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.lru_n(1), vec![(&"A", &1)]);
+    /// ```
+    pub fn lru_n(&self, n: usize) -> Vec<(&K, &V)> {
+        self.indices_from_tail()
+            .take(n)
+            .map(|index| self.entry_at(index))
+            .collect()
+    }
+
+    /// Renvoie la génération courante : un compteur incrémenté à chaque
+    /// modification d'une valeur (insertion, mise à jour, modification en place).
+    pub fn current_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Renvoie les entrées modifiées après la génération `generation`, de la plus
+    /// récemment utilisée à la moins récemment utilisée. Permet de synchroniser
+    /// un stockage en aval par incréments : relever `current_generation`, puis
+    /// demander les changements depuis cette génération au tour suivant.
+    ///
+    /// Les suppressions et les évictions n'apparaissent pas.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// let synced = cache.current_generation();
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.changed_since(synced), vec![(&"B", &2)]);
+    /// ```
+    pub fn changed_since(&self, generation: u64) -> Vec<(&K, &V)> {
+        self.indices_from_head()
+            .map(|index| self.node(index))
+            .filter(|node| node.modified > generation)
+            .map(|node| (&node.key, &node.value))
+            .collect()
+    }
+
+    /// Passe à `writer` chaque entrée modifiée depuis le dernier appel (ou depuis la
+    /// création du cache), de la moins récemment utilisée à la plus récente, puis
+    /// les considère toutes comme propres. Une entrée est salie par une insertion,
+    /// une mise à jour ou un accès mutable (`peek_mut_if`, `get_many_mut`...) :
+    /// de quoi persister par lots uniquement ce qui a changé (cache à écriture
+    /// différée). Les entrées supprimées ou évincées avant le vidage sont perdues.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// let mut written = Vec::new();
+    /// cache.flush_dirty(|key, value| written.push((*key, *value)));
+    /// assert_eq!(written, vec![("A", 1)]);
+    /// cache.flush_dirty(|_, _| unreachable!());
+    /// ```
+    pub fn flush_dirty<F: FnMut(&K, &V)>(&mut self, mut writer: F) {
+        for index in self.indices_from_tail() {
+            let node = self.node(index);
+            if node.modified > self.flushed {
+                writer(&node.key, &node.value);
+            }
+        }
+        self.flushed = self.generation;
+    }
+
+    /// Renvoie les clés des entrées lues ou mises à jour au moins `min_accesses` fois
+    /// depuis leur insertion (celle-ci ne compte pas), de la plus récemment utilisée
+    /// à la moins récente. Le décompte est tenu quelle que soit la stratégie
+    /// d'éviction ; il aide à dimensionner un segment protégé ou à choisir les
+    /// entrées à épingler.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.get(&"A");
+    /// assert_eq!(cache.hot_set(1), vec![&"A"]);
+    /// ```
+    pub fn hot_set(&self, min_accesses: usize) -> Vec<&K> {
+        self.indices_from_head()
+            .map(|index| self.node(index))
+            .filter(|node| node.access_count >= min_accesses)
+            .map(|node| &node.key)
+            .collect()
+    }
+
+    /// Compare ce cache à `other`, par exemple pour vérifier la cohérence de deux
+    /// réplicas : clés propres à chacun, et clés communes dont les valeurs
+    /// diffèrent. Ni l'un ni l'autre n'est modifié ; les échéances sont ignorées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut a: Cache<&str, i32> = Cache::new(3);
+    /// let mut b: Cache<&str, i32> = Cache::new(3);
+    /// a.put("A", 1);
+    /// b.put("A", 2);
+    /// b.put("B", 3);
+    /// let diff = a.diff(&b);
+    /// assert!(diff.only_in_self.is_empty());
+    /// assert_eq!(diff.only_in_other, vec![&"B"]);
+    /// assert_eq!(diff.differing, vec![(&"A", &1, &2)]);
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Cache<K, V>) -> CacheDiff<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        let mut diff = CacheDiff {
+            only_in_self: Vec::new(),
+            only_in_other: Vec::new(),
+            differing: Vec::new(),
+        };
+        for (key, value) in self.indices_from_head().map(|index| self.entry_at(index)) {
+            match other.map.get(key) {
+                None => diff.only_in_self.push(key),
+                Some(&index) => {
+                    let theirs = &other.node(index).value;
+                    if value != theirs {
+                        diff.differing.push((key, value, theirs));
+                    }
+                }
+            }
+        }
+        diff.only_in_other = other
+            .indices_from_head()
+            .map(|index| &other.node(index).key)
+            .filter(|key| !self.map.contains_key(*key))
+            .collect();
+        diff
+    }
+
+    /// Consomme le cache et renvoie toutes ses entrées triées par clé, par exemple
+    /// pour produire un rapport déterministe (l'ordre d'utilisation est ignoré).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("B", 2);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.into_sorted_vec(), vec![("A", 1), ("B", 2)]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<(K, V)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<(K, V)> = self
+            .slots
+            .into_iter()
+            .flatten()
+            .map(|node| (node.key, node.value))
+            .collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Consomme le cache et renvoie sa capacité et ses entrées, de la plus récemment
+    /// utilisée à la moins récente. Avec `from_parts`, permet de sérialiser le cache
+    /// dans n'importe quel format sans perdre l'ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.into_parts(), (3, vec![("B", 2), ("A", 1)]));
+    /// ```
+    pub fn into_parts(mut self) -> (usize, Vec<(K, V)>) {
+        let order: Vec<usize> = self.indices_from_head().collect();
+        let entries = order
+            .into_iter()
+            .map(|index| {
+                let node = self.slots[index].take().unwrap();
+                (node.key, node.value)
+            })
+            .collect();
+        (self.capacity, entries)
+    }
+
+    /// Reconstruit un cache de capacité `capacity` à partir d'entrées rangées de la
+    /// plus récemment utilisée à la moins récente, comme les renvoie `into_parts`.
+    /// S'il y a plus d'entrées que de places, les moins récentes sont évincées.
+    ///
+    /// Sert aussi à amorcer un cache dans un ordre d'utilisation connu, par exemple
+    /// d'après des statistiques d'accès. Pour une clé en double, l'occurrence la plus
+    /// proche du début l'emporte.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache = Cache::from_parts(2, vec![("B", 2), ("A", 1)]);
+    /// cache.put("C", 3); // Évince "A"
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn from_parts(capacity: usize, entries: Vec<(K, V)>) -> Self {
+        let mut cache = Cache::new(capacity);
+        for (key, value) in entries.into_iter().rev() {
+            cache.put(key, value);
+        }
+        cache
+    }
+
+    /// Crée un cache déjà rempli, dans un ordre d'utilisation connu : la première
+    /// entrée de `entries` est la plus récemment utilisée, la dernière la moins
+    /// récente. Au-delà de `capacity`, seules les plus récentes sont gardées.
+    /// Équivaut à `from_parts`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache = Cache::with_initial_entries(2, vec![("A", 1), ("B", 2), ("C", 3)]);
+    /// assert_eq!(cache.get(&"C"), None);
+    /// assert_eq!(cache.get(&"B"), Some(&2));
+    /// ```
+    pub fn with_initial_entries(capacity: usize, entries: Vec<(K, V)>) -> Self {
+        Cache::from_parts(capacity, entries)
+    }
+
+    /// Renvoie une entrée tirée uniformément au hasard, sans modifier l'ordre
+    /// d'utilisation (fonctionnalité `rand`). Le tirage parcourt les entrées : il est
+    /// destiné à l'analyse et aux expérimentations, pas aux chemins critiques.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// assert_eq!(cache.random_entry(), None);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.random_entry(), Some((&"A", &1)));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random_entry(&self) -> Option<(&K, &V)> {
+        self.random_entry_with(&mut rand::thread_rng())
+    }
+
+    /// Comme `random_entry`, en tirant avec le générateur `rng`, par exemple un
+    /// générateur à graine fixe pour obtenir des tirages reproductibles.
+    #[cfg(feature = "rand")]
+    pub fn random_entry_with<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<(&K, &V)> {
+        if self.map.is_empty() {
+            return None;
+        }
+        let position = rng.gen_range(0..self.map.len());
+        let node = self.slots.iter().flatten().nth(position)?;
+        Some((&node.key, &node.value))
+    }
+
+    /// Renvoie les clés dans l'ordre de la liste interne, de la plus récemment
+    /// utilisée à la moins récemment utilisée, pour vérifier l'ordre exact dans
+    /// les tests.
+    ///
+    /// Disponible avec la fonctionnalité `testing`.
+    #[cfg(feature = "testing")]
+    pub fn recency_order(&self) -> Vec<K> {
+        self.indices_from_head()
+            .map(|index| self.node(index).key.clone())
+            .collect()
+    }
+
+    /// Insère une paire clé-valeur seulement si aucune éviction n'est nécessaire :
+    /// la clé existe déjà, ou il reste de la place (en nombre d'entrées comme en
+    /// poids). Sinon, la clé et la valeur sont rendues dans `Err`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(1);
+    /// assert_eq!(cache.put_no_evict("A", 1), Ok(()));
+    /// assert_eq!(cache.put_no_evict("B", 2), Err(("B", 2)));
+    /// assert_eq!(cache.put_no_evict("A", 10), Ok(()));
+    /// ```
+    pub fn put_no_evict(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        let weight = self.weigh(&key, &value);
+        let (is_new, old_weight) = match self.map.get(&key) {
+            Some(&index) => (false, self.node(index).weight),
+            None => (true, 0),
+        };
+        let new_total = (self.total_weight - old_weight).saturating_add(weight);
+        if (is_new && self.map.len() >= self.capacity) || new_total > self.max_weight {
+            return Err((key, value));
+        }
+        self.put(key, value);
+        Ok(())
+    }
+
+    /// Marque une entrée comme utilisée sans lire sa valeur. Renvoie `false` si
+    /// la clé est absente (ou expirée).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert!(cache.touch(&"A"));
+    /// cache.put("C", 3); // Évince "B"
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn touch(&mut self, key: &K) -> bool {
+        match self.find_live(key) {
+            Some(index) => {
+                self.promote(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// L'inverse de `touch` : fait d'une entrée la prochaine candidate à l'éviction,
+    /// en la plaçant en queue de liste. Renvoie `false` si la clé est absente (ou
+    /// expirée).
+    ///
+    /// En mode CLOCK, son bit de référence est effacé ; en mode LFU, sa fréquence
+    /// est remise à zéro. Une entrée épinglée reste protégée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert!(cache.demote(&"B"));
+    /// cache.put("C", 3); // Évince "B"
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn demote(&mut self, key: &K) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        let node = self.node_mut(index);
+        node.referenced = false;
+        node.hits = 0;
+        node.frequency = 0;
+        self.remove_node(index);
+        self.add_to_tail(index);
+        true
+    }
+
+    /// Insère une entrée (ou met à jour une clé présente) au rang `rank` de l'ordre
+    /// d'utilisation : 0 pour la plus récemment utilisée, `len` pour la moins
+    /// récente, un rang supérieur étant ramené à `len`. Les évictions nécessaires
+    /// ont lieu avant le placement, comme pour `put`. Utile pour préparer l'état
+    /// d'un test ou amorcer un cache dans un ordre connu.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.insert_at(1, "B", 2); // "B" devient la moins récemment utilisée
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn insert_at(&mut self, rank: usize, key: K, value: V) {
+        let Some(index) = self.insert_entry(key, value, self.eviction_sink()) else {
+            return;
+        };
+        self.remove_node(index);
+        let next = self.indices_from_head().nth(rank);
+        match next {
+            Some(next) => self.add_before(index, next),
+            None => self.add_to_tail(index),
+        }
+        self.debug_validate();
+    }
+
+    /// Vérifie la présence d'une clé et, si elle est présente (et non expirée), la
+    /// marque comme utilisée, en une seule opération et sans emprunter la valeur.
+    ///
+    /// Équivaut à `touch` ; le nom exprime l'intention lorsque la présence compte
+    /// autant que la promotion.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// assert!(cache.contains_and_touch(&"A"));
+    /// assert!(!cache.contains_and_touch(&"B"));
+    /// ```
+    pub fn contains_and_touch(&mut self, key: &K) -> bool {
+        self.touch(key)
+    }
+
+    /// Comme `get`, mais renvoie aussi la clé stockée : utile lorsque la clé
+    /// conservée porte plus d'informations que celle de la recherche (clé
+    /// canonique, internée...). L'entrée est promue.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get_entry(&"A"), Some((&"A", &1)));
+    /// assert_eq!(cache.get_entry(&"B"), None);
+    /// ```
+    pub fn get_entry(&mut self, key: &K) -> Option<(&K, &V)> {
+        let index = self.live_index(key)?;
+        self.promote(index);
+        Some(self.entry_at(index))
+    }
+
+    /// Lit plusieurs clés d'un coup : chaque clé demandée est renvoyée avec une
+    /// copie de sa valeur, ou `None` si elle est absente ou expirée. Les entrées
+    /// trouvées sont promues dans l'ordre de `keys`, la dernière devenant la plus
+    /// récemment utilisée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get_batch(&["A", "B"]), vec![("A", Some(1)), ("B", None)]);
+    /// ```
+    pub fn get_batch(&mut self, keys: &[K]) -> Vec<(K, Option<V>)>
+    where
+        V: Clone,
+    {
+        keys.iter()
+            .map(|key| {
+                let value = self.live_index(key).map(|index| {
+                    self.promote(index);
+                    self.node(index).value.clone()
+                });
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Renvoie la valeur associée à `key` sans la promouvoir, ou `default` si la clé
+    /// est absente ou expirée. Rien n'est inséré : la valeur par défaut reste à
+    /// l'appelant, et une entrée expirée n'est pas supprimée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(*cache.peek_or(&"A", &0), 1);
+    /// assert_eq!(*cache.peek_or(&"B", &0), 0);
+    /// ```
+    pub fn peek_or<'a>(&'a self, key: &K, default: &'a V) -> &'a V {
+        let Some(&index) = self.map.get(key) else {
+            return default;
+        };
+        let node = self.node(index);
+        match node.expires_at {
+            Some(deadline) if deadline <= (self.clock.0)() => default,
+            _ => &node.value,
+        }
+    }
+
+    /// Interne `key` : renvoie la clé déjà stockée qui lui est égale, ou stocke
+    /// `key` elle-même. Le cache sert alors d'interneur borné (par exemple
+    /// `Cache<Arc<str>, ()>`) : des valeurs égales partagent le même handle tant
+    /// qu'elles restent chaudes, et les moins récemment internées sont évincées. Les
+    /// handles déjà distribués restent valides après l'éviction ; seule la
+    /// déduplication des suivants est perdue.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut interner: Cache<Arc<str>, ()> = Cache::new(100);
+    /// let a = interner.intern(Arc::from("mot"));
+    /// let b = interner.intern(Arc::from("mot"));
+    /// assert!(Arc::ptr_eq(&a, &b));
+    /// ```
+    pub fn intern(&mut self, key: K) -> K
+    where
+        V: Default,
+    {
+        if let Some(index) = self.live_index(&key) {
+            self.promote(index);
+            return self.node(index).key.clone();
+        }
+        self.insert_entry(key.clone(), V::default(), self.eviction_sink());
+        key
+    }
+
+    /// Rejoue une séquence d'accès enregistrée pour reconstruire l'ordre
+    /// d'utilisation : chaque clé présente est promue avec `touch`, les clés
+    /// absentes sont ignorées. Aucune valeur n'est modifiée.
+    pub fn replay_accesses(&mut self, keys: &[K]) {
+        for key in keys {
+            self.touch(key);
+        }
+    }
+
+    /// Charge par avance les clés absentes : `loader` est appelé pour chaque clé
+    /// manquante (ou expirée) et le résultat est inséré s'il existe. Les entrées
+    /// déjà présentes ne sont ni rechargées ni promues.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<u32, u32> = Cache::new(4);
+    /// cache.put(1, 10);
+    /// cache.prefetch(&[1, 2, 3], |key| (*key != 3).then(|| key * 100));
+    /// assert_eq!(cache.get(&1), Some(&10));
+    /// assert_eq!(cache.get(&2), Some(&200));
+    /// assert_eq!(cache.get(&3), None);
+    /// ```
+    pub fn prefetch(&mut self, keys: &[K], loader: impl Fn(&K) -> Option<V>) {
+        for key in keys {
+            if self.find_live(key).is_some() {
+                continue;
+            }
+            if let Some(value) = loader(key) {
+                self.put(key.clone(), value);
+            }
+        }
+    }
+
+    /// Retire les `n` entrées les moins récemment utilisées et les renvoie dans un
+    /// nouveau cache de capacité `n`, en conservant leur ordre relatif et leurs
+    /// échéances. Le nouveau cache reprend la stratégie d'éviction, mais pas le
+    /// pondérateur.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// let mut cold = cache.split_off_lru(1);
+    /// assert_eq!(cold.get(&"A"), Some(&1));
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn split_off_lru(&mut self, n: usize) -> Cache<K, V> {
+        let mut cold = Cache::with_policy(n, self.policy);
+        cold.lfu_tiebreak = self.lfu_tiebreak;
+        cold.duplicate_policy = self.duplicate_policy;
+        cold.clock = self.clock.clone();
+        cold.map =
+            HashMap::with_capacity_and_hasher(n.min(PRESIZE_LIMIT), self.map.hasher().clone());
+        for _ in 0..n {
+            let Some(tail) = self.tail else {
+                break;
+            };
+            let node = self.take_index(tail);
+            // Insérées de la plus ancienne à la plus récente
+            if let Some(index) = cold.insert_entry(node.key, node.value, |_, _| {}) {
+                cold.node_mut(index).expires_at = node.expires_at;
+            }
+        }
+        cold
+    }
+
+    /// Ne garde que les `n` entrées les plus récemment utilisées et renvoie les
+    /// autres, de la plus récemment utilisée à la moins récente. Contrairement à
+    /// `resize`, la capacité est inchangée ; comme pour une suppression explicite,
+    /// les entrées épinglées au-delà des `n` premières sont retirées aussi.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.retain_most_recent(1), vec![("B", 2), ("A", 1)]);
+    /// assert_eq!(cache.capacity(), 3);
+    /// ```
+    pub fn retain_most_recent(&mut self, n: usize) -> Vec<(K, V)> {
+        let dropped: Vec<usize> = self.indices_from_head().skip(n).collect();
+        dropped
+            .into_iter()
+            .map(|index| {
+                let node = self.take_index(index);
+                (node.key, node.value)
+            })
+            .collect()
+    }
+
+    /// Retire les entrées en partant de la moins récemment utilisée tant que `pred`
+    /// est vrai, et les renvoie dans l'ordre de retrait. Le parcours s'arrête à la
+    /// première entrée qui ne vérifie pas `pred` ; les entrées épinglées sont
+    /// sautées sans l'interrompre.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 5);
+    /// cache.put("C", 2);
+    /// assert_eq!(cache.evict_while(|_, v| *v < 3), vec![("A", 1)]);
+    /// ```
+    pub fn evict_while<F: FnMut(&K, &V) -> bool>(&mut self, mut pred: F) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+        let mut cursor = self.tail;
+        while let Some(index) = cursor {
+            cursor = self.node(index).prev;
+            if self.is_pinned(index) {
+                continue;
+            }
+            let node = self.node(index);
+            if !pred(&node.key, &node.value) {
+                break;
+            }
+            let node = self.take_index(index);
+            evicted.push((node.key, node.value));
+        }
+        evicted
+    }
+
+    /// Renvoie la valeur associée à `key`, ou calcule `f` en cas d'absence puis
+    /// insère et renvoie le résultat (lecture à travers le cache).
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// assert_eq!(*cache.get_or_insert_with("A", || 1), 1);
+    /// assert_eq!(*cache.get_or_insert_with("A", || 2), 1);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &V {
+        let index = match self.live_index(&key) {
+            Some(index) => {
+                self.promote(index);
+                Some(index)
+            }
+            None => {
+                let value = f();
+                self.insert_loaded(|| key, value)
+            }
+        };
+        self.stored_value(index)
+    }
+
+    /// Comme `get_or_insert_with`, mais le chargeur reçoit la clé : inutile de la
+    /// capturer lorsque la valeur en dépend.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, usize> = Cache::new(3);
+    /// assert_eq!(*cache.get_or_insert_with_key("abc", |key| key.len()), 3);
+    /// ```
+    pub fn get_or_insert_with_key<F: FnOnce(&K) -> V>(&mut self, key: K, f: F) -> &V {
+        let index = match self.live_index(&key) {
+            Some(index) => {
+                self.promote(index);
+                Some(index)
+            }
+            None => {
+                let value = f(&key);
+                self.insert_loaded(|| key, value)
+            }
+        };
+        self.stored_value(index)
+    }
+
+    /// Comme `get_or_insert_with`, mais une valeur présente que `is_stale` juge
+    /// périmée est remplacée par `refresh()`, comme par un `put`. Une valeur fraîche
+    /// est renvoyée telle quelle, et une clé absente reçoit `refresh()`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", -1);
+    /// assert_eq!(*cache.get_or_refresh("A", |v| *v < 0, || 1), 1);
+    /// assert_eq!(*cache.get_or_refresh("A", |v| *v < 0, || 2), 1);
+    /// ```
+    pub fn get_or_refresh<P, F>(&mut self, key: K, is_stale: P, refresh: F) -> &V
+    where
+        P: Fn(&V) -> bool,
+        F: FnOnce() -> V,
+    {
+        let index = match self.live_index(&key) {
+            Some(index) if !is_stale(&self.node(index).value) => {
+                self.promote(index);
+                Some(index)
+            }
+            _ => {
+                let value = refresh();
+                self.insert_loaded(|| key, value)
+            }
+        };
+        self.stored_value(index)
+    }
+
+    /// Comme `get_or_insert_with`, mais à partir d'une clé empruntée : la clé n'est
+    /// clonée qu'en cas d'absence, lorsqu'une entrée est effectivement insérée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::Cache;
+    ///
+    /// let mut cache: Cache<String, usize> = Cache::new(3);
+    /// let key = "A".to_string();
+    /// assert_eq!(*cache.get_or_insert_with_ref(&key, || 1), 1);
+    /// assert_eq!(*cache.get_or_insert_with_ref(&key, || 2), 1);
+    /// ```
+    pub fn get_or_insert_with_ref<F: FnOnce() -> V>(&mut self, key: &K, f: F) -> &V {
+        let index = match self.live_index(key) {
+            Some(index) => {
+                self.promote(index);
+                Some(index)
+            }
+            None => {
+                let value = f();
+                self.insert_loaded(|| key.clone(), value)
+            }
+        };
+        self.stored_value(index)
+    }
+
+    /// Renvoie la valeur associée à `key`, ou attend `f` en cas d'absence puis
+    /// insère et renvoie le résultat (lecture à travers le cache avec un chargeur
+    /// asynchrone). Le futur est attendu avant l'insertion : aucune entrée n'est
+    /// empruntée pendant l'attente.
+    ///
+    /// Disponible avec la fonctionnalité `async`.
+    #[cfg(feature = "async")]
+    pub async fn get_or_compute<F, Fut>(&mut self, key: K, f: F) -> &V
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = V>,
+    {
+        let index = match self.live_index(&key) {
+            Some(index) => {
+                self.promote(index);
+                Some(index)
+            }
+            None => {
+                let value = f().await;
+                self.insert_loaded(|| key, value)
+            }
+        };
+        self.stored_value(index)
+    }
+
+    /// Vide le cache.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.slots.clear();
+        self.free.clear();
+        self.head = None;
+        self.tail = None;
+        self.total_weight = 0;
+        self.negative.clear();
+        if let Some(bloom) = self.bloom.as_mut() {
+            bloom.clear();
+        }
+        self.emit(CacheEvent::Clear);
+    }
+
+    /// Vide le cache et rend la mémoire de la table à l'allocateur.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.clear_and_shrink();
+    /// assert!(cache.is_empty());
+    /// ```
+    pub fn clear_and_shrink(&mut self) {
+        self.clear();
+        self.map.shrink_to_fit();
+        self.slots.shrink_to_fit();
+        self.free.shrink_to_fit();
+        self.negative.shrink_to_fit();
+    }
+
+    /// Réduit la capacité du cache à son nombre d'entrées (au moins 1) et libère la
+    /// mémoire inutilisée si le cache n'a pas servi depuis `idle_since`. Renvoie
+    /// `true` si le cache a été réduit. Les entrées et leur ordre sont conservés.
+    ///
+    /// Destinée à être appelée périodiquement par un minuteur. Pour ne rien coûter
+    /// aux accès, le cache ne date pas chacun d'eux : il compte les accès, et chaque
+    /// appel relève l'instant auquel une nouvelle activité a été constatée. Le
+    /// dernier accès est donc daté à la précision des appels : un cache utilisé
+    /// depuis l'appel précédent n'est jamais considéré comme inactif.
+    ///
+    /// La capacité d'origine n'est pas rétablie automatiquement.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(100);
+    /// cache.put("A", 1);
+    /// // Le cache vient de servir : il n'est pas réduit
+    /// assert!(!cache.maybe_shrink(Instant::now() - Duration::from_secs(60)));
+    /// ```
+    pub fn maybe_shrink(&mut self, idle_since: Instant) -> bool {
+        if self.accesses != self.idle_check.0 {
+            self.idle_check = (self.accesses, (self.clock.0)());
+        }
+        if self.idle_check.1 >= idle_since {
+            return false;
+        }
+        self.resize(self.len().max(1));
+        self.compact_slots();
+        self.map.shrink_to_fit();
+        self.free.shrink_to_fit();
+        self.negative.shrink_to_fit();
+        true
+    }
+
+    /// Fige le cache en lecture seule. Le `FrozenCache` obtenu n'expose aucune
+    /// opération de modification, ce qui convient pour partager un cache précalculé.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// let frozen = cache.freeze();
+    /// assert_eq!(frozen.get(&"A"), Some(&1));
+    /// ```
+    pub fn freeze(self) -> FrozenCache<K, V> {
+        FrozenCache { cache: self }
+    }
+
+    /// Vérifie que le cache ne contient pas plus d'entrées que sa capacité.
+    ///
+    /// # Panics
+    ///
+    /// Panique si l'invariant est violé, ce qui révèle une erreur dans la logique
+    /// d'éviction. Destinée aux tests.
+    pub fn assert_within_capacity(&self) {
+        assert!(
+            self.map.len() <= self.capacity,
+            "le cache contient {} entrées pour une capacité de {}",
+            self.map.len(),
+            self.capacity
+        );
+    }
+
+    /// Vérifie la cohérence interne du cache : chaînage de la liste dans les deux
+    /// sens, correspondance entre la table et les emplacements, comptabilité des
+    /// emplacements libres et du poids total. Renvoie la première incohérence
+    /// trouvée.
+    ///
+    /// La vérification parcourt tout le cache. Dans les compilations de débogage,
+    /// elle est exécutée après chaque `put`, `remove` et `resize`, qui deviennent
+    /// donc linéaires ; elle disparaît des compilations optimisées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        let (mut count, mut weight) = (0, 0);
+        let mut prev = None;
+        let mut current = self.head;
+        while let Some(index) = current {
+            let node = self
+                .slots
+                .get(index)
+                .and_then(Option::as_ref)
+                .ok_or_else(|| format!("le lien vers l'emplacement {} est vide", index))?;
+            if node.prev != prev {
+                return Err(format!("lien `prev` incohérent à l'emplacement {}", index));
+            }
+            if self.map.get(&node.key) != Some(&index) {
+                return Err(format!("la table ne désigne pas l'emplacement {}", index));
+            }
+            count += 1;
+            if count > self.map.len() {
+                return Err("la liste est plus longue que la table".to_string());
+            }
+            weight += node.weight;
+            prev = current;
+            current = node.next;
+        }
+        if self.tail != prev {
+            return Err("la queue ne désigne pas le dernier nœud de la liste".to_string());
+        }
+        if count != self.map.len() {
+            return Err(format!(
+                "la liste contient {} nœuds pour {} entrées",
+                count,
+                self.map.len()
+            ));
+        }
+        if self.slots.len() - self.free.len() != count {
+            return Err("les emplacements libres sont mal comptés".to_string());
+        }
+        if weight != self.total_weight {
+            return Err(format!(
+                "poids total de {} pour une somme des poids de {}",
+                self.total_weight, weight
+            ));
+        }
+        Ok(())
+    }
+
+    /// Décrit le chaînage de la liste pour diagnostiquer une corruption : les clés
+    /// vues en parcourant la liste de la tête vers la queue, puis de la queue vers
+    /// la tête, et les anomalies constatées (parcours interrompu sur un emplacement
+    /// vide, plus long que la table, ou asymétrique). Complément lisible de
+    /// `validate`, qui ne rapporte que la première incohérence.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert!(cache.link_report().contains("liens cohérents"));
+    /// ```
+    pub fn link_report(&self) -> String
+    where
+        K: fmt::Debug,
+    {
+        let (forward, forward_issue) = self.walk_links(self.head, |node| node.next);
+        let (backward, backward_issue) = self.walk_links(self.tail, |node| node.prev);
+        let keys = |indices: &[usize]| {
+            let keys: Vec<&K> = indices.iter().map(|&index| &self.node(index).key).collect();
+            format!("{:?}", keys)
+        };
+        let mut report = format!(
+            "tête → queue : {}\nqueue → tête : {}\n",
+            keys(&forward),
+            keys(&backward)
+        );
+
+        let mut issues: Vec<String> = [forward_issue, backward_issue]
+            .into_iter()
+            .flatten()
+            .collect();
+        for (direction, walked) in [("tête → queue", &forward), ("queue → tête", &backward)] {
+            if walked.len() != self.map.len() {
+                issues.push(format!(
+                    "{} : {} entrées parcourues pour {} dans la table",
+                    direction,
+                    walked.len(),
+                    self.map.len()
+                ));
+            }
+        }
+        if !forward.iter().eq(backward.iter().rev()) {
+            issues.push("les deux parcours ne sont pas l'inverse l'un de l'autre".to_string());
+        }
+
+        if issues.is_empty() {
+            report.push_str(&format!(
+                "liens cohérents : les deux parcours voient les {} entrées en ordre inverse",
+                forward.len()
+            ));
+        } else {
+            for issue in issues {
+                report.push_str(&format!("anomalie : {}\n", issue));
+            }
+            report.pop();
+        }
+        report
+    }
+
+    /// Suit les liens depuis `start` avec `step` sans supposer la liste saine :
+    /// le parcours s'arrête sur un emplacement vide, ou après plus de nœuds que la
+    /// table n'en compte (cycle probable), en décrivant l'anomalie.
+    fn walk_links<F>(&self, start: Option<usize>, step: F) -> (Vec<usize>, Option<String>)
+    where
+        F: Fn(&Node<K, V>) -> Option<usize>,
+    {
+        let mut walked = Vec::new();
+        let mut current = start;
+        while let Some(index) = current {
+            let Some(node) = self.slots.get(index).and_then(Option::as_ref) else {
+                return (
+                    walked,
+                    Some(format!("lien vers l'emplacement vide {}", index)),
+                );
+            };
+            if walked.len() == self.map.len() {
+                return (
+                    walked,
+                    Some(format!("cycle probable à l'emplacement {}", index)),
+                );
+            }
+            walked.push(index);
+            current = step(node);
+        }
+        (walked, None)
+    }
+
+    /// Reconstruit entièrement le chaînage de la liste (liens `prev`/`next`, tête et
+    /// queue) d'après la table, pour réparer un cache dont `validate` signale des
+    /// liens incohérents. L'ordre d'utilisation devient `order`, de la plus
+    /// récemment utilisée à la moins récente : les clés absentes du cache (et les
+    /// doublons) sont ignorées, et les entrées qui n'y figurent pas sont placées en
+    /// queue, dans l'ordre de leurs emplacements.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.rebuild_links(&["A", "B"]);
+    /// assert_eq!(cache.validate(), Ok(()));
+    /// cache.put("C", 3); // "B" est désormais la moins récemment utilisée
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn rebuild_links(&mut self, order: &[K]) {
+        let mut listed = HashSet::new();
+        let mut sequence: Vec<usize> = order
+            .iter()
+            .filter_map(|key| self.map.get(key).copied())
+            .filter(|&index| listed.insert(index))
+            .collect();
+        let mut rest: Vec<usize> = self
+            .map
+            .values()
+            .copied()
+            .filter(|index| !listed.contains(index))
+            .collect();
+        rest.sort_unstable();
+        sequence.extend(rest);
+
+        for (position, &index) in sequence.iter().enumerate() {
+            let node = self.node_mut(index);
+            node.prev = position.checked_sub(1).map(|previous| sequence[previous]);
+            node.next = sequence.get(position + 1).copied();
+        }
+        self.head = sequence.first().copied();
+        self.tail = sequence.last().copied();
+    }
+
+    /// Renvoie le nœud rangé à l'emplacement `index`.
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.slots[index].as_ref().unwrap()
+    }
+
+    /// Renvoie le nœud rangé à l'emplacement `index`, en écriture.
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.slots[index].as_mut().unwrap()
+    }
+
+    /// Renvoie la clé et la valeur rangées à l'emplacement `index`.
+    fn entry_at(&self, index: usize) -> (&K, &V) {
+        let node = self.node(index);
+        (&node.key, &node.value)
+    }
+
+    /// Parcourt les emplacements de la tête (MRU) vers la queue (LRU).
+    fn indices_from_head(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.head, |&index| self.node(index).next)
+    }
+
+    /// Parcourt les emplacements de la queue (LRU) vers la tête (MRU).
+    fn indices_from_tail(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.tail, |&index| self.node(index).prev)
+    }
+
+    /// Range les nœuds de façon contiguë, dans l'ordre de la liste, et libère les
+    /// emplacements inutilisés.
+    fn compact_slots(&mut self) {
+        let order: Vec<usize> = self.indices_from_head().collect();
+        let mut slots = Vec::with_capacity(order.len());
+        for (position, index) in order.into_iter().enumerate() {
+            let mut node = self.slots[index].take().unwrap();
+            node.prev = position.checked_sub(1);
+            node.next = Some(position + 1);
+            *self.map.get_mut(&node.key).unwrap() = position;
+            slots.push(Some(node));
+        }
+        if let Some(Some(last)) = slots.last_mut() {
+            last.next = None;
+        }
+        self.head = (!slots.is_empty()).then_some(0);
+        self.tail = slots.len().checked_sub(1);
+        self.slots = slots;
+        self.free.clear();
+    }
+
+    /// Vérifie les invariants avec `validate` dans les compilations de débogage ;
+    /// disparaît des compilations optimisées.
+    fn debug_validate(&self) {
+        debug_assert_eq!(self.validate(), Ok(()), "invariant du cache violé");
+    }
+
+    /// Trace une entrée évincée pour faire de la place (fonctionnalité `logging`).
+    fn log_eviction(&self, _key: &K) {
+        #[cfg(feature = "logging")]
+        log::trace!("entrée évincée : {}", LoggedKey(_key, self.key_fmt));
+    }
+
+    /// Trace une recherche infructueuse (fonctionnalité `logging`).
+    fn log_miss(&self, _key: &K) {
+        #[cfg(feature = "logging")]
+        log::debug!("clé absente du cache : {}", LoggedKey(_key, self.key_fmt));
+    }
+
+    /// Diffuse un événement aux abonnés, en oubliant ceux qui se sont désabonnés.
+    fn emit(&mut self, event: CacheEvent) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event).is_ok());
+    }
+
+    /// Renvoie le rappel d'éviction des insertions ordinaires : il transmet les
+    /// entrées évincées au canal de `eviction_channel`, s'il est ouvert.
+    fn eviction_sink(&self) -> impl FnMut(K, V) {
+        let sender = self.evictions.clone();
+        move |key, value| {
+            if let Some(sender) = &sender {
+                sender.send((key, value)).ok();
+            }
+        }
+    }
+
+    /// Enregistre une insertion par `put` dans la fenêtre de croissance automatique,
+    /// et fait grandir le cache si la fenêtre le justifie.
+    fn observe_autogrow(&mut self, full: bool, miss: bool) {
+        let capacity = self.capacity;
+        let Some(autogrow) = self.autogrow.as_mut() else {
+            return;
+        };
+        if !full {
+            autogrow.puts = 0;
+            autogrow.misses = 0;
+            return;
+        }
+        autogrow.puts += 1;
+        autogrow.misses += usize::from(miss);
+        if autogrow.puts < capacity.max(1) {
+            return;
+        }
+        let grow = autogrow.misses * 2 >= autogrow.puts && capacity < autogrow.max_capacity;
+        let target = ((capacity as f64 * autogrow.growth_factor).ceil() as usize)
+            .max(capacity + 1)
+            .min(autogrow.max_capacity);
+        autogrow.puts = 0;
+        autogrow.misses = 0;
+        if grow {
+            self.resize(target);
+        }
+    }
+
+    /// Marque la valeur rangée à l'emplacement `index` comme modifiée à une nouvelle
+    /// génération.
+    fn mark_modified(&mut self, index: usize) {
+        self.generation += 1;
+        let generation = self.generation;
+        self.node_mut(index).modified = generation;
+    }
+
+    /// Range un nouveau nœud dans un emplacement libre et renvoie son indice.
+    fn allocate(&mut self, node: Node<K, V>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(node);
+                index
+            }
+            None => {
+                self.slots.push(Some(node));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Supprime un nœud de sa position actuelle dans la liste.
+    fn remove_node(&mut self, index: usize) {
+        let node = self.node(index);
+        let (prev, next) = (node.prev, node.next);
+
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Ajoute un nœud en tête de la liste (le plus récemment utilisé).
+    fn add_to_head(&mut self, index: usize) {
+        let old_head = self.head;
+        let node = self.node_mut(index);
+        node.prev = None;
+        node.next = old_head;
+
+        if let Some(old_head) = old_head {
+            self.node_mut(old_head).prev = Some(index);
+        }
+
+        self.head = Some(index);
+
+        if self.tail.is_none() {
+            self.tail = Some(index);
+        }
+    }
+
+    /// Ajoute un nœud en queue de la liste (le moins récemment utilisé).
+    fn add_to_tail(&mut self, index: usize) {
+        let old_tail = self.tail;
+        let node = self.node_mut(index);
+        node.prev = old_tail;
+        node.next = None;
+
+        if let Some(old_tail) = old_tail {
+            self.node_mut(old_tail).next = Some(index);
+        }
+
+        self.tail = Some(index);
+
+        if self.head.is_none() {
+            self.head = Some(index);
+        }
+    }
+
+    /// Insère un nœud détaché juste avant `next`, c'est-à-dire un rang plus près de
+    /// la tête.
+    fn add_before(&mut self, index: usize, next: usize) {
+        let prev = self.node(next).prev;
+        let node = self.node_mut(index);
+        node.prev = prev;
+        node.next = Some(next);
+        self.node_mut(next).prev = Some(index);
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(index),
+            None => self.head = Some(index),
+        }
+    }
+
+    /// Déplace un nœud en tête de la liste (le marque comme le plus récemment utilisé).
+    fn move_to_head(&mut self, index: usize) {
+        if self.head == Some(index) {
+            return;
+        }
+        self.remove_node(index);
+        self.add_to_head(index);
+    }
+
+    /// Calcule le poids d'une entrée (0 sans pondérateur).
+    fn weigh(&self, key: &K, value: &V) -> usize {
+        match &self.weigher {
+            Some(weigher) => (weigher.0)(key, value),
+            None => 0,
+        }
+    }
+
+    /// Marque une entrée présente comme utilisée, selon la stratégie d'éviction.
+    fn promote(&mut self, index: usize) {
+        if self.timed_accesses {
+            let now = (self.clock.0)();
+            self.node_mut(index).last_access = Some(now);
+        }
+        self.node_mut(index).access_count += 1;
+        match self.policy {
+            EvictionPolicy::Lru => {
+                let threshold = self.promote_threshold;
+                let node = self.node_mut(index);
+                node.hits += 1;
+                if node.hits >= threshold {
+                    node.hits = 0;
+                    self.move_to_head(index);
+                }
+            }
+            // En mode CLOCK, un accès ne modifie pas la liste
+            EvictionPolicy::Clock => self.node_mut(index).referenced = true,
+            EvictionPolicy::Lfu => {
+                self.node_mut(index).frequency += 1;
+                self.move_to_head(index);
+            }
+        }
+    }
+
+    /// Renvoie l'emplacement de l'entrée si elle est présente et non expirée ; une
+    /// entrée expirée est supprimée. La recherche compte dans les statistiques :
+    /// réservée aux lectures (`get`, `get_or_insert_with`...), les autres opérations
+    /// passent par `find_live`.
+    fn live_index(&mut self, key: &K) -> Option<usize> {
+        self.accesses += 1;
+        let index = self.find_live(key);
+        if index.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+            self.log_miss(key);
+        }
+        index
+    }
+
+    /// Cherche l'entrée de `live_index`, sans tenir les compteurs.
+    fn find_live(&mut self, key: &K) -> Option<usize> {
+        if let Some(bloom) = &self.bloom {
+            if !bloom.may_contain(self.map.hasher().hash_one(key)) {
+                return None;
+            }
+        }
+        let index = *self.map.get(key)?;
+        let expires_at = self.node(index).expires_at;
+        if expires_at.is_some_and(|deadline| deadline <= (self.clock.0)()) {
+            self.take_index(index);
+            return None;
+        }
+        Some(index)
+    }
+
+    /// Retire une entrée de la liste et de la table, en tenant le poids total à jour.
+    fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let index = *self.map.get(key)?;
+        let node = self.take_index(index);
+        Some((node.key, node.value))
+    }
+
+    /// Retire le nœud rangé à l'emplacement `index` et libère l'emplacement.
+    fn take_index(&mut self, index: usize) -> Node<K, V> {
+        self.remove_node(index);
+        let node = self.slots[index].take().unwrap();
+        self.free.push(index);
+        self.map.remove(&node.key);
+        self.track_key(&node.key, false);
+        self.total_weight -= node.weight;
+        node
+    }
+
+    /// Tient le filtre de Bloom, s'il est activé, à jour après l'ajout (`added`) ou
+    /// le retrait d'une clé.
+    fn track_key(&mut self, key: &K, added: bool) {
+        if let Some(bloom) = self.bloom.as_mut() {
+            let hash = self.map.hasher().hash_one(key);
+            if added {
+                bloom.insert(hash);
+            } else {
+                bloom.remove(hash);
+            }
+        }
+    }
+
+    /// Supprime le nœud le moins récemment utilisé (en queue de liste) et le renvoie,
+    /// en passant les entrées épinglées.
+    fn remove_tail(&mut self) -> Option<(K, V)> {
+        let floor = self.priority_floor();
+        let victim = self
+            .indices_from_tail()
+            .find(|&index| self.is_evictable(index, floor))?;
+        let node = self.take_index(victim);
+        Some((node.key, node.value))
+    }
+
+    /// Renvoie la plus faible priorité parmi les entrées non épinglées, seules
+    /// candidates à l'éviction, ou `None` si aucune priorité n'a jamais été donnée.
+    fn priority_floor(&self) -> Option<u8> {
+        if !self.prioritized {
+            return None;
+        }
+        self.indices_from_head()
+            .filter(|&index| !self.is_pinned(index))
+            .map(|index| self.node(index).priority)
+            .min()
+    }
+
+    /// Indique si l'entrée rangée à l'emplacement `index` peut être évincée : elle
+    /// n'est pas épinglée et sa priorité est la plus faible (`floor`).
+    fn is_evictable(&self, index: usize, floor: Option<u8>) -> bool {
+        !self.is_pinned(index) && floor.is_none_or(|floor| self.node(index).priority == floor)
+    }
+
+    /// Indique si l'entrée rangée à l'emplacement `index` est protégée de l'éviction.
+    fn is_pinned(&self, index: usize) -> bool {
+        let node = self.node(index);
+        node.pinned
+            || self
+                .pin_predicate
+                .as_ref()
+                .is_some_and(|pinned| (pinned.0)(&node.key, &node.value))
+    }
+
+    /// Libère une place selon la stratégie d'éviction du cache et renvoie l'entrée évincée.
+    fn evict(&mut self) -> Option<(K, V)> {
+        let evicted = match self.policy {
+            EvictionPolicy::Lru => self.remove_tail(),
+            EvictionPolicy::Clock => self.clock_sweep(),
+            EvictionPolicy::Lfu => {
+                let node = self.take_index(self.lfu_victim()?);
+                Some((node.key, node.value))
+            }
+        };
+        if let Some((key, _)) = &evicted {
+            self.log_eviction(key);
+        }
+        evicted
+    }
+
+    /// Cherche l'entrée de plus faible fréquence. La liste est parcourue depuis
+    /// l'extrémité désignée par le départage, et seule une fréquence strictement
+    /// plus faible remplace le candidat courant.
+    fn lfu_victim(&self) -> Option<usize> {
+        let candidates: Box<dyn Iterator<Item = usize>> = match self.lfu_tiebreak {
+            LfuTiebreak::OldestFirst => Box::new(self.indices_from_tail()),
+            LfuTiebreak::NewestFirst => Box::new(self.indices_from_head()),
+        };
+        let floor = self.priority_floor();
+        let mut victim: Option<(usize, usize)> = None;
+        for index in candidates.filter(|&index| self.is_evictable(index, floor)) {
+            let frequency = self.node(index).frequency;
+            if victim.is_none_or(|(_, lowest)| frequency < lowest) {
+                victim = Some((index, frequency));
+            }
+        }
+        victim.map(|(index, _)| index)
+    }
+
+    /// Balayage CLOCK : la queue de liste joue le rôle de l'aiguille. Une entrée
+    /// référencée perd son bit et repart en tête (seconde chance), la première
+    /// entrée non référencée est évincée.
+    fn clock_sweep(&mut self) -> Option<(K, V)> {
+        // Deux tours suffisent : le premier efface tous les bits de référence
+        let mut steps = 2 * self.map.len();
+        let floor = self.priority_floor();
+        while let Some(tail) = self.tail {
+            if steps == 0 {
+                // Toutes les entrées sont épinglées
+                return None;
+            }
+            steps -= 1;
+            if !self.is_evictable(tail, floor) {
+                self.move_to_head(tail);
+                continue;
+            }
+            let node = self.node_mut(tail);
+            if node.referenced {
+                node.referenced = false;
+                self.move_to_head(tail);
+            } else {
+                return self.remove_tail();
+            }
+        }
+        None
+    }
+
+    /// Évince des entrées jusqu'à ce qu'une entrée de poids `incoming_weight` tienne
+    /// (y compris une place libre si `new_entry` est vrai), en passant chaque entrée
+    /// évincée à `on_evict`.
+    fn make_room<F: FnMut(K, V)>(
+        &mut self,
+        new_entry: bool,
+        incoming_weight: usize,
+        on_evict: &mut F,
+    ) {
+        if new_entry && self.above_high_watermark() {
+            let (_, low) = self.watermarks.unwrap();
+            while self.map.len() > low {
+                match self.evict() {
+                    Some((key, value)) => on_evict(key, value),
+                    None => break,
+                }
+            }
+        }
+        while self.tail.is_some()
+            && ((new_entry && self.map.len() >= self.capacity)
+                || self.total_weight.saturating_add(incoming_weight) > self.max_weight)
+        {
+            match self.evict() {
+                Some((key, value)) => on_evict(key, value),
+                None => break,
+            }
+        }
+    }
+
+    /// Indique si le cache a atteint le seuil haut de l'éviction par lots.
+    fn above_high_watermark(&self) -> bool {
+        self.watermarks
+            .is_some_and(|(high, _)| self.map.len() >= high)
+    }
+
+    /// Insère une paire clé-valeur en passant à `on_evict` chaque entrée évincée pour
+    /// faire de la place, et renvoie l'emplacement de l'entrée. Un cache de capacité
+    /// nulle refuse toute nouvelle entrée : la valeur est mise de côté pour
+    /// `stored_value` et `None` est renvoyé.
+    fn insert_entry<F: FnMut(K, V)>(&mut self, key: K, value: V, mut on_evict: F) -> Option<usize> {
+        self.accesses += 1;
+        let weight = self.weigh(&key, &value);
+        if !self.negative.is_empty() {
+            // La clé a désormais une valeur : elle n'est plus connue comme absente
+            self.negative.remove(&key);
+        }
+
+        // Sans éviction à prévoir, la clé est rangée dans la table par la même recherche
+        // que celle qui constate son absence ; sinon, elle n'y entre qu'une fois la
+        // place faite, au prix d'un second hachage.
+        let needs_room = self.map.len() >= self.capacity
+            || self.total_weight.saturating_add(weight) > self.max_weight
+            || self.above_high_watermark();
+        let (key, reserved) = match self.map.entry(key) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                self.update_entry(index, value, weight, &mut on_evict);
+                return Some(index);
+            }
+            Entry::Vacant(entry) if !needs_room => {
+                let key = entry.key().clone();
+                let index = self.free.last().copied().unwrap_or(self.slots.len());
+                entry.insert(index);
+                (key, Some(index))
+            }
+            Entry::Vacant(entry) => (entry.into_key(), None),
+        };
+
+        if self.capacity == 0 {
+            self.rejected = Some(value);
+            return None;
+        }
+
+        let node = Node {
+            key: key.clone(),
+            value,
+            prev: None,
+            next: None,
+            referenced: false,
+            weight,
+            expires_at: None,
+            frequency: 1,
+            hits: 0,
+            modified: 0,
+            pinned: false,
+            last_access: self.timed_accesses.then(|| (self.clock.0)()),
+            access_count: 0,
+            priority: 0,
+        };
+        if let (1, None, Some(index)) = (self.capacity, &self.weigher, self.head) {
+            // Capacité 1 : l'unique nœud est remplacé sur place, sans toucher à la liste.
+            // Une entrée épinglée n'est pas remplacée, et le cache peut alors dépasser sa
+            // capacité : il ne compte plus un seul nœud.
+            if self.tail == Some(index) && self.is_evictable(index, None) {
+                let old = std::mem::replace(self.node_mut(index), node);
+                self.track_key(&old.key, false);
+                self.track_key(&key, true);
+                self.map.remove(&old.key);
+                self.map.insert(key, index);
+                self.log_eviction(&old.key);
+                on_evict(old.key, old.value);
+                self.mark_modified(index);
+                return Some(index);
+            }
+        }
+
+        let index = match reserved {
+            Some(index) => {
+                let allocated = self.allocate(node);
+                debug_assert_eq!(allocated, index);
+                self.track_key(&key, true);
+                index
+            }
+            None => {
+                self.make_room(true, weight, &mut on_evict);
+                let index = self.allocate(node);
+                self.track_key(&key, true);
+                self.map.insert(key, index);
+                index
+            }
+        };
+        self.mark_modified(index);
+        self.total_weight += weight;
+        self.add_to_head(index);
+        Some(index)
+    }
+
+    /// Remplace la valeur de l'entrée présente à `index` et la promeut, en évinçant
+    /// d'autres entrées si son nouveau poids dépasse le poids maximal.
+    fn update_entry<F: FnMut(K, V)>(
+        &mut self,
+        index: usize,
+        value: V,
+        weight: usize,
+        on_evict: &mut F,
+    ) {
+        let node = self.slots[index].as_mut().unwrap();
+        self.total_weight = self.total_weight - node.weight + weight;
+        node.value = value;
+        node.weight = weight;
+        node.expires_at = None;
+        self.mark_modified(index);
+        self.promote(index);
+        if self.total_weight > self.max_weight {
+            // L'entrée mise à jour est détachée pour ne pas être sa propre victime
+            self.remove_node(index);
+            self.make_room(false, 0, on_evict);
+            self.add_to_head(index);
+        }
+    }
+
+    /// Insère une valeur produite par un chargeur (`get_or_insert_with`...). Avec une
+    /// capacité nulle, la clé n'est même pas construite : la valeur est seulement
+    /// mise de côté pour être renvoyée par `stored_value`.
+    fn insert_loaded<Q: FnOnce() -> K>(&mut self, key: Q, value: V) -> Option<usize> {
+        if self.capacity == 0 {
+            // Cache désactivé : la valeur chargée est renvoyée sans tenter d'insertion
+            self.rejected = Some(value);
+            return None;
+        }
+        self.insert_entry(key(), value, self.eviction_sink())
+    }
+
+    /// Renvoie la valeur de l'entrée rangée à `index`, ou à défaut la dernière valeur
+    /// refusée par `insert_entry`.
+    fn stored_value(&self, index: Option<usize>) -> &V {
+        match index {
+            Some(index) => &self.node(index).value,
+            None => self.rejected.as_ref().expect("aucune valeur refusée"),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> LRUCache<K, V> for Cache<K, V> {
+    /// Insère une paire clé-valeur dans le cache.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// ```
+    fn put(&mut self, key: K, value: V) {
+        let observed = self.autogrow.is_some().then(|| {
+            (
+                self.map.len() >= self.capacity,
+                !self.map.contains_key(&key),
+            )
+        });
+        self.insert_entry(key, value, self.eviction_sink());
+        if let Some((full, miss)) = observed {
+            self.observe_autogrow(full, miss);
+        }
+        self.debug_validate();
+    }
+
+    /// Récupère une valeur du cache par sa clé.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<&str, i32> = Cache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// ```
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self.live_index(key)?;
+        self.promote(index);
+        Some(&self.node(index).value)
+    }
+}
+
+/// Affiche la capacité, le nombre d'entrées et les entrées de la plus récemment
+/// utilisée à la moins récente, sans les détails internes de la liste.
+impl<K, V> fmt::Debug for Cache<K, V>
+where
+    K: Eq + Hash + Clone + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .field("entries", &DebugEntries(self))
+            .finish()
+    }
+}
+
+/// Entrées d'un cache dans l'ordre d'utilisation, pour son `Debug`.
+struct DebugEntries<'a, K: Eq + Hash + Clone, V>(&'a Cache<K, V>);
+
+impl<K, V> fmt::Debug for DebugEntries<'_, K, V>
+where
+    K: Eq + Hash + Clone + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cache = self.0;
+        f.debug_map()
+            .entries(cache.indices_from_head().map(|index| cache.entry_at(index)))
+            .finish()
+    }
+}
+
+/// Un cache figé en lecture seule, obtenu avec `Cache::freeze`.
+///
+/// Les lectures ne modifient pas l'ordre d'utilisation : `get` se comporte comme
+/// `peek`. Les entrées expirées restent stockées mais ne sont plus renvoyées.
+#[derive(Debug)]
+pub struct FrozenCache<K: Eq + Hash + Clone, V> {
+    cache: Cache<K, V>,
+}
+
+impl<K: Eq + Hash + Clone, V> FrozenCache<K, V> {
+    /// Récupère une valeur par sa clé, sans la promouvoir.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.peek(key)
+    }
+
+    /// Récupère une valeur par sa clé, sans la promouvoir.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let node = self.cache.node(*self.cache.map.get(key)?);
+        self.is_live(node).then_some(&node.value)
+    }
+
+    /// Renvoie le nombre d'entrées stockées.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Parcourt les entrées non expirées, de la plus récemment utilisée à la moins
+    /// récemment utilisée.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.cache
+            .indices_from_head()
+            .map(|index| self.cache.node(index))
+            .filter(|node| self.is_live(node))
+            .map(|node| (&node.key, &node.value))
+    }
+
+    fn is_live(&self, node: &Node<K, V>) -> bool {
+        node.expires_at
+            .is_none_or(|deadline| deadline > (self.cache.clock.0)())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Parcourt la liste de la tête (MRU) vers la queue (LRU).
+    fn recency<K: Eq + Hash + Clone, V>(cache: &Cache<K, V>) -> Vec<K> {
+        cache
+            .indices_from_head()
+            .map(|index| cache.node(index).key.clone())
+            .collect()
+    }
+
+    /// Renvoie le nœud d'une clé présente.
+    fn node<'a, K: Eq + Hash + Clone, V>(cache: &'a Cache<K, V>, key: &K) -> &'a Node<K, V> {
+        cache.node(cache.map[key])
+    }
+
+    /// Traduit un lien (indice d'emplacement) en clé.
+    fn key_at<K: Eq + Hash + Clone, V>(cache: &Cache<K, V>, link: Option<usize>) -> Option<K> {
+        link.map(|index| cache.node(index).key.clone())
+    }
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = Cache::new(3); // Taille de 3
+        cache.put("A", String::from("value_a"));
+        cache.put("B", String::from("value_b"));
+        cache.put("C", String::from("value_c"));
+        cache.put("D", String::from("value_d"));
+        // Cache == [B, C, D]
+
+        let my_value = cache.get(&"A");
+        assert_eq!(my_value, None);
+
+        let my_value = cache.get(&"D");
+        assert_eq!(my_value, Some(&String::from("value_d")));
+        // Cache == [B, C, D]
+
+        let my_value = cache.get(&"B");
+        assert_eq!(my_value, Some(&String::from("value_b")));
+        // Cache == [C, D, B]
+
+        let my_value = cache.get(&"C");
+        assert_eq!(my_value, Some(&String::from("value_c")));
+        // Cache == [D, B, C]
+
+        let my_value = cache.get(&"X");
+        assert_eq!(my_value, None);
+        // Cache == [D, B, C]
+
+        cache.put("A", String::from("value_a"));
+        // Cache == [B, C, A]
+
+        cache.put("X", String::from("value_x"));
+        // Cache == [C, A, X]
+
+        let my_value = cache.get(&"B");
+        assert_eq!(my_value, None);
+        // Cache == [C, A, X]
+
+        let my_value = cache.get(&"D");
+        assert_eq!(my_value, None);
+        // Cache == [C, A, X]
+    }
+
+    #[test]
+    fn test_persistent_cache() {
+        let filename = "test_cache.txt";
+
+        {
+            let mut cache: Cache<String, String> = Cache::new_persistent(3, filename);
+            cache.put("A".to_string(), "value_a".to_string());
+            cache.put("B".to_string(), "value_b".to_string());
+            cache.save_to_file(filename).unwrap();
+        }
+
+        {
+            let mut cache: Cache<String, String> = Cache::new_persistent(3, filename);
+            assert_eq!(cache.get(&"A".to_string()), Some(&"value_a".to_string()));
+            assert_eq!(cache.get(&"B".to_string()), Some(&"value_b".to_string()));
+        }
+
+        // Nettoyage du fichier de test
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_file_format_version() {
+        let filename = "test_cache_version.txt";
+        let mut cache: Cache<String, u32> = Cache::new(3);
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        cache.save_to_file(filename).unwrap();
+        let content = std::fs::read_to_string(filename).unwrap();
+        assert_eq!(content, "#cachelru v1\nA\t1\nB\t2\n");
+
+        let mut reloaded: Cache<String, u32> = Cache::new(3);
+        reloaded.load_from_file(filename).unwrap();
+        assert_eq!(recency(&reloaded), vec!["B", "A"]);
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_file_format_version() {
+        let filename = "test_cache_bad_version.txt";
+        type Loader = fn(&mut Cache<String, u32>, &str) -> io::Result<()>;
+        let loaders: [(&str, Loader); 6] = [
+            ("load_from_file", |cache, f| cache.load_from_file(f)),
+            ("load_recent_from_file", |cache, f| {
+                cache.load_recent_from_file(f, 3)
+            }),
+            ("load_from_files", |cache, f| {
+                cache.load_from_files(&[f], MergeStrategy::Overwrite)
+            }),
+            ("load_from_file_ordered", |cache, f| {
+                cache.load_from_file_ordered(f)
+            }),
+            ("load_from_file_with", |cache, f| {
+                cache.load_from_file_with(f, |v| v.parse().ok())
+            }),
+            ("load_checkpoints_from_file", |cache, f| {
+                cache.load_checkpoints_from_file(f)
+            }),
+        ];
+        for (name, load) in loaders {
+            for header in ["#cachelru v2", "#cachelru vX", "#cachelru"] {
+                std::fs::write(filename, format!("{header}\nA\t1\n0\tA\t1\n")).unwrap();
+                let mut cache: Cache<String, u32> = Cache::new(3);
+                let error = load(&mut cache, filename).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{name}");
+                assert!(error.to_string().contains("version"), "{name}");
+                assert!(cache.is_empty(), "{name}");
+            }
+
+            // L'en-tête de la version courante est accepté
+            std::fs::write(filename, "#cachelru v1\nA\t1\n0\tA\t1\n").unwrap();
+            let mut cache: Cache<String, u32> = Cache::new(3);
+            load(&mut cache, filename).unwrap();
+            assert_eq!(cache.get(&"A".to_string()), Some(&1), "{name}");
+        }
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_reader_writer_round_trip() {
+        let data: &[u8] = b"A\tvalue_a\nB\tvalue_b\n";
+        let mut cache: Cache<String, String> = Cache::new(3);
+        cache.load_from_reader(std::io::Cursor::new(data)).unwrap();
+        assert_eq!(cache.get(&"A".to_string()), Some(&"value_a".to_string()));
+        assert_eq!(cache.get(&"B".to_string()), Some(&"value_b".to_string()));
+
+        let mut buffer: Vec<u8> = Vec::new();
+        cache.save_to_writer(&mut buffer).unwrap();
+
+        // Rechargement depuis le tampon produit
+        let mut reloaded: Cache<String, String> = Cache::new(3);
+        reloaded.load_from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(reloaded.get(&"A".to_string()), Some(&"value_a".to_string()));
+        assert_eq!(reloaded.get(&"B".to_string()), Some(&"value_b".to_string()));
+        assert_eq!(reloaded.map.len(), 2);
+    }
+
+    #[test]
+    fn test_clock_policy_second_chance() {
+        let mut cache = Cache::with_policy(3, EvictionPolicy::Clock);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+
+        // "A" est la plus ancienne entrée, mais elle est référencée
+        assert_eq!(cache.get(&"A"), Some(&1));
+        // La liste n'est pas modifiée par l'accès
+        assert_eq!(key_at(&cache, cache.tail), Some("A"));
+
+        cache.put("D", 4);
+        // "A" a eu une seconde chance, "B" est évincée
+        assert!(cache.map.contains_key(&"A"));
+        assert!(!cache.map.contains_key(&"B"));
+        assert!(cache.map.contains_key(&"C"));
+        assert!(cache.map.contains_key(&"D"));
+        // Le bit de "A" a été effacé pendant le balayage
+        assert!(!node(&cache, &"A").referenced);
+
+        cache.put("E", 5);
+        // Sans nouvel accès, "C" est la suivante à partir
+        assert!(!cache.map.contains_key(&"C"));
+        assert_eq!(cache.map.len(), 3);
+    }
+
+    #[test]
+    fn test_map_values() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        cache.get(&"A");
+        // Cache == [B, C, A]
+
+        let mut mapped: Cache<&str, String> = cache.map_values(|v| v.to_string());
+        assert_eq!(mapped.capacity, 3);
+        assert_eq!(recency(&mapped), vec!["A", "C", "B"]);
+        assert_eq!(mapped.get(&"A"), Some(&"1".to_string()));
+        assert_eq!(mapped.get(&"B"), Some(&"2".to_string()));
+        assert_eq!(mapped.get(&"C"), Some(&"3".to_string()));
+
+        // Le cache d'origine est inchangé
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+    }
+
+    #[test]
+    fn test_load_recent_from_file() {
+        let filename = "test_cache_recent.txt";
+        {
+            let mut file = File::create(filename).unwrap();
+            for i in 0..1000 {
+                writeln!(file, "{}\t{}", i, i * 10).unwrap();
+            }
+        }
+
+        let mut cache: Cache<u32, u32> = Cache::new(10);
+        cache.load_recent_from_file(filename, 3).unwrap();
+        assert_eq!(cache.map.len(), 3);
+        // La dernière ligne du fichier est la plus récemment utilisée
+        assert_eq!(recency(&cache), vec![999, 998, 997]);
+        assert_eq!(cache.get(&998), Some(&9980));
+        assert_eq!(cache.get(&996), None);
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_save_preserves_recency_order() {
+        let mut cache = Cache::new(3);
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        cache.put("C".to_string(), 3);
+        cache.get(&"A".to_string());
+
+        let mut buffer: Vec<u8> = Vec::new();
+        cache.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(buffer, b"B\t2\nC\t3\nA\t1\n");
+
+        let mut reloaded: Cache<String, u32> = Cache::new(3);
+        reloaded.load_from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(recency(&reloaded), recency(&cache));
+    }
+
+    #[test]
+    fn test_insert_reporting() {
+        let mut cache = Cache::new(2);
+        assert_eq!(cache.insert_reporting("A", 1), (None, &1));
+        assert_eq!(cache.insert_reporting("B", 2), (None, &2));
+
+        // Le cache déborde : "A" est évincée et la valeur insérée est lisible aussitôt
+        let (evicted, value) = cache.insert_reporting("C", 3);
+        assert_eq!(evicted, Some(("A", 1)));
+        assert_eq!(*value, 3);
+
+        // Une mise à jour n'évince rien
+        let (evicted, value) = cache.insert_reporting("B", 20);
+        assert_eq!(evicted, None);
+        assert_eq!(*value, 20);
+        assert_eq!(recency(&cache), vec!["B", "C"]);
+
+        // Plusieurs évictions : la première est renvoyée, les autres vont au canal
+        let mut cache: Cache<&str, String> = Cache::with_weigher(10, 6, |_, v: &String| v.len());
+        let evictions = cache.eviction_channel();
+        cache.put("A", "aa".to_string());
+        cache.put("B", "bb".to_string());
+        let (evicted, value) = cache.insert_reporting("C", "cccccc".to_string());
+        assert_eq!(evicted, Some(("A", "aa".to_string())));
+        assert_eq!(value, "cccccc");
+        assert_eq!(evictions.try_recv(), Ok(("B", "bb".to_string())));
+    }
+
+    #[test]
+    fn test_total_weight() {
+        let mut cache: Cache<&str, String> = Cache::with_weigher(10, 10, |_, v: &String| v.len());
+        assert_eq!(cache.max_weight(), Some(10));
+        cache.put("A", "aaaa".to_string());
+        cache.put("B", "bbb".to_string());
+        cache.put("C", "cc".to_string());
+        assert_eq!(cache.total_weight(), 9);
+
+        // 9 + 5 > 10 : "A" (poids 4) est évincée
+        cache.put("D", "ddddd".to_string());
+        assert!(!cache.map.contains_key(&"A"));
+        assert_eq!(cache.total_weight(), 10);
+
+        assert_eq!(cache.remove(&"B"), Some("bbb".to_string()));
+        assert_eq!(cache.total_weight(), 7);
+
+        // Une mise à jour remplace l'ancien poids
+        cache.put("C", "c".to_string());
+        assert_eq!(cache.total_weight(), 6);
+
+        // Une mise à jour plus lourde évince les autres entrées
+        cache.put("C", "cccccccc".to_string());
+        assert_eq!(recency(&cache), vec!["C"]);
+        assert_eq!(cache.total_weight(), 8);
+
+        // Sans pondérateur, le poids reste nul
+        let mut plain = Cache::new(2);
+        plain.put("A", 1);
+        assert_eq!(plain.total_weight(), 0);
+        assert_eq!(plain.max_weight(), None);
+    }
+
+    #[test]
+    fn test_put_sorted_is_deterministic() {
+        let source: HashMap<u32, u32> = (0..50).map(|i| (i, i * 2)).collect();
+        // Une seconde table avec une autre graine de hachage
+        let other: HashMap<u32, u32> = source.iter().map(|(k, v)| (*k, *v)).collect();
+
+        let mut first = Cache::new(50);
+        first.put_sorted(source);
+        let mut second = Cache::new(50);
+        second.put_sorted(other);
+        assert_eq!(recency(&first), recency(&second));
+        assert_eq!(recency(&first), (0..50).rev().collect::<Vec<u32>>());
+
+        // Les évictions suivent le même ordre
+        for i in 50..60 {
+            first.put(i, 0);
+            second.put(i, 0);
+        }
+        assert_eq!(recency(&first), recency(&second));
+        assert!(!first.map.contains_key(&9));
+        assert!(first.map.contains_key(&10));
+    }
+
+    /// Horloge manuelle partagée entre le test et le cache.
+    fn manual_clock<K: Eq + Hash + Clone, V>(cache: &mut Cache<K, V>) -> Arc<Mutex<Instant>> {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let handle = Arc::clone(&now);
+        cache.set_clock(move || *handle.lock().unwrap());
+        now
+    }
+
+    #[test]
+    fn test_get_sliding() {
+        let mut cache = Cache::new(3);
+        let now = manual_clock(&mut cache);
+        let ttl = Duration::from_secs(10);
+        cache.put_with_ttl("A", 1, ttl);
+
+        // Des accès réguliers dans la fenêtre repoussent l'échéance
+        for _ in 0..5 {
+            *now.lock().unwrap() += Duration::from_secs(8);
+            assert_eq!(cache.get_sliding(&"A", ttl), Some(&1));
+        }
+
+        // Au-delà de `ttl` sans accès, l'entrée expire
+        *now.lock().unwrap() += Duration::from_secs(10);
+        assert_eq!(cache.get_sliding(&"A", ttl), None);
+        assert!(!cache.map.contains_key(&"A"));
+        assert_eq!(cache.get_sliding(&"X", ttl), None);
+
+        // Une durée trop grande pour l'horloge retire l'échéance au lieu de paniquer
+        cache.put_with_ttl("B", 2, ttl);
+        assert_eq!(cache.get_sliding(&"B", Duration::MAX), Some(&2));
+        assert_eq!(cache.ttl_remaining(&"B"), None);
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5)] {
+            cache.put(key, value);
+        }
+        // Cache == [A, B, C, D, E]
+
+        let extracted = cache.extract_if(|_, v| v % 2 == 1);
+        assert_eq!(extracted, vec![("E", 5), ("C", 3), ("A", 1)]);
+        assert_eq!(recency(&cache), vec!["D", "B"]);
+        assert_eq!(key_at(&cache, cache.tail), Some("B"));
+        assert_eq!(cache.get(&"B"), Some(&2));
+        assert_eq!(cache.get(&"C"), None);
+    }
+
+    #[test]
+    fn test_lfu_tiebreak() {
+        for (tiebreak, victim) in [
+            (LfuTiebreak::OldestFirst, "B"),
+            (LfuTiebreak::NewestFirst, "C"),
+        ] {
+            let mut cache = Cache::with_policy(3, EvictionPolicy::Lfu);
+            cache.set_lfu_tiebreak(tiebreak);
+            cache.put("A", 1);
+            cache.put("B", 2);
+            cache.put("C", 3);
+            cache.get(&"A");
+            // "A" a une fréquence de 2, "B" et "C" sont à égalité à 1
+
+            cache.put("D", 4);
+            assert!(cache.map.contains_key(&"A"));
+            assert!(!cache.map.contains_key(&victim));
+            assert_eq!(cache.map.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_update_in_place_keeps_links() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        // Cache == [A, B, C]
+
+        assert_eq!(cache.update_in_place(&"B", 20), Ok(2));
+        assert_eq!(node(&cache, &"B").value, 20);
+        assert_eq!(key_at(&cache, node(&cache, &"B").prev), Some("C"));
+        assert_eq!(key_at(&cache, node(&cache, &"B").next), Some("A"));
+        assert_eq!(key_at(&cache, node(&cache, &"C").next), Some("B"));
+        assert_eq!(key_at(&cache, node(&cache, &"A").prev), Some("B"));
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+
+        assert_eq!(cache.update_in_place(&"X", 0), Err(0));
+        assert!(!cache.map.contains_key(&"X"));
+    }
+
+    #[test]
+    fn test_utilization() {
+        let mut cache = Cache::new(4);
+        assert_eq!(cache.utilization(), 0.0);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        assert_eq!(cache.utilization(), 0.5);
+        cache.put("C", 3);
+        cache.put("D", 4);
+        cache.put("E", 5);
+        assert_eq!(cache.utilization(), 1.0);
+
+        let empty: Cache<&str, i32> = Cache::new(0);
+        assert_eq!(empty.utilization(), 0.0);
+    }
+
+    #[test]
+    fn test_mru_n_and_lru_n() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        cache.get(&"A");
+        // Cache == [B, C, D, A]
+
+        assert_eq!(cache.mru_n(2), vec![(&"A", &1), (&"D", &4)]);
+        assert_eq!(cache.lru_n(2), vec![(&"B", &2), (&"C", &3)]);
+        assert_eq!(cache.mru_n(10).len(), 4);
+        assert_eq!(cache.lru_n(10)[3], (&"A", &1));
+        assert!(cache.mru_n(0).is_empty());
+        // Aucune promotion
+        assert_eq!(recency(&cache), vec!["A", "D", "C", "B"]);
+    }
+
+    #[test]
+    fn test_put_no_evict() {
+        let mut cache = Cache::new(2);
+        assert_eq!(cache.put_no_evict("A", 1), Ok(()));
+        assert_eq!(cache.put_no_evict("B", 2), Ok(()));
+        assert_eq!(cache.put_no_evict("C", 3), Err(("C", 3)));
+        assert_eq!(recency(&cache), vec!["B", "A"]);
+
+        // Une clé existante est mise à jour normalement
+        assert_eq!(cache.put_no_evict("A", 10), Ok(()));
+        assert_eq!(cache.get(&"A"), Some(&10));
+
+        // Le budget de poids est aussi respecté
+        let mut weighted: Cache<&str, String> = Cache::with_weigher(10, 5, |_, v: &String| v.len());
+        assert_eq!(weighted.put_no_evict("A", "aaa".to_string()), Ok(()));
+        assert_eq!(
+            weighted.put_no_evict("B", "bbb".to_string()),
+            Err(("B", "bbb".to_string()))
+        );
+        assert_eq!(weighted.len(), 1);
+    }
+
+    #[test]
+    fn test_replay_accesses() {
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        cache.get(&"B");
+        cache.get(&"D");
+        // Cache == [A, C, B, D]
+
+        cache.replay_accesses(&["C", "X", "A", "D", "B"]);
+        assert_eq!(recency(&cache), vec!["B", "D", "A", "C"]);
+        assert!(!cache.map.contains_key(&"X"));
+        assert_eq!(node(&cache, &"A").value, 1);
+    }
+
+    /// Écrit un fichier de cache contenant deux fois la clé "A".
+    fn write_duplicate_file(filename: &str) {
+        let mut file = File::create(filename).unwrap();
+        write!(file, "A\tfirst\nB\tvalue_b\nA\tlast\n").unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_policy_last_wins() {
+        let filename = "test_cache_dup_last.txt";
+        write_duplicate_file(filename);
+        let mut cache: Cache<String, String> = Cache::new(3);
+        cache.load_from_file(filename).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(recency(&cache), vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(cache.get(&"A".to_string()), Some(&"last".to_string()));
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_policy_first_wins() {
+        let filename = "test_cache_dup_first.txt";
+        write_duplicate_file(filename);
+        let mut cache: Cache<String, String> = Cache::new(3);
+        cache.set_duplicate_policy(DuplicatePolicy::FirstWins);
+        cache.load_from_file(filename).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(recency(&cache), vec!["B".to_string(), "A".to_string()]);
+        assert_eq!(cache.get(&"A".to_string()), Some(&"first".to_string()));
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_policy_error() {
+        let filename = "test_cache_dup_error.txt";
+        write_duplicate_file(filename);
+        let mut cache: Cache<String, String> = Cache::new(3);
+        cache.set_duplicate_policy(DuplicatePolicy::Error);
+        let err = cache.load_from_file(filename).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // Rien n'a été inséré
+        assert!(cache.is_empty());
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_split_off_lru() {
+        let mut cache = Cache::new(6);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5), ("F", 6)] {
+            cache.put(key, value);
+        }
+        cache.get(&"A");
+        // Cache == [B, C, D, E, F, A]
+
+        let cold = cache.split_off_lru(3);
+        assert_eq!(cold.capacity(), 3);
+        assert_eq!(recency(&cold), vec!["D", "C", "B"]);
+        assert_eq!(recency(&cache), vec!["A", "F", "E"]);
+        assert_eq!(cache.len(), 3);
+
+        // Demander plus que le contenu vide le cache
+        let rest = cache.split_off_lru(10);
+        assert_eq!(recency(&rest), vec!["A", "F", "E"]);
+        assert!(cache.is_empty());
+        assert_eq!(cache.head, None);
+        assert_eq!(cache.tail, None);
+    }
+
+    #[test]
+    fn test_binding_limit() {
+        // Le poids est atteint avant le nombre d'entrées
+        let mut cache: Cache<&str, u32> = Cache::with_weigher(10, 6, |_, v: &u32| *v as usize);
+        assert_eq!(cache.limits(), (10, Some(6)));
+        cache.put("A", 3);
+        cache.put("B", 3);
+        cache.put("C", 1);
+        assert_eq!(recency(&cache), vec!["C", "B"]);
+        assert_eq!(cache.total_weight(), 4);
+
+        // Le nombre d'entrées est atteint avant le poids
+        let mut cache: Cache<&str, u32> = Cache::with_weigher(2, 100, |_, v: &u32| *v as usize);
+        assert_eq!(cache.limits(), (2, Some(100)));
+        cache.put("A", 1);
+        cache.put("B", 1);
+        cache.put("C", 1);
+        assert_eq!(recency(&cache), vec!["C", "B"]);
+        assert_eq!(cache.total_weight(), 2);
+
+        let plain: Cache<&str, u32> = Cache::new(5);
+        assert_eq!(plain.limits(), (5, None));
+    }
+
+    /// Chargeur asynchrone : rend la main au runtime avant de produire sa valeur.
+    #[cfg(feature = "async")]
+    async fn load_later(value: i32) -> i32 {
+        tokio::task::yield_now().await;
+        value
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_or_compute() {
+        let mut cache = Cache::new(2);
+        let mut calls = 0;
+
+        let value = cache
+            .get_or_compute("A", || {
+                calls += 1;
+                load_later(1)
+            })
+            .await;
+        assert_eq!(*value, 1);
+
+        // Présente : le chargeur n'est pas appelé
+        let value = cache
+            .get_or_compute("A", || {
+                calls += 1;
+                load_later(2)
+            })
+            .await;
+        assert_eq!(*value, 1);
+        assert_eq!(calls, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_clear_and_shrink() {
+        let mut cache = Cache::new(100);
+        for i in 0..100 {
+            cache.put(i, i);
+        }
+        assert!(cache.map.capacity() >= 100);
+
+        cache.clear_and_shrink();
+        assert!(cache.is_empty());
+        assert_eq!(cache.map.capacity(), 0);
+        assert_eq!(cache.head, None);
+        assert_eq!(cache.tail, None);
+
+        // Le cache reste utilisable
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.get(&1), Some(&10));
+        assert_eq!(recency(&cache), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_capacity_invariant_under_churn() {
+        for policy in [
+            EvictionPolicy::Lru,
+            EvictionPolicy::Clock,
+            EvictionPolicy::Lfu,
+        ] {
+            let mut cache = Cache::with_policy(16, policy);
+            // Générateur pseudo-aléatoire simple et déterministe
+            let mut seed: u64 = 42;
+            for _ in 0..5000 {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let key = (seed >> 33) % 64;
+                match seed % 4 {
+                    0 => {
+                        cache.get(&key);
+                    }
+                    1 => {
+                        cache.remove(&key);
+                    }
+                    _ => cache.put(key, seed),
+                }
+                cache.assert_within_capacity();
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_within_capacity_panics() {
+        let mut cache = Cache::new(1);
+        cache.put("A", 1);
+        cache.capacity = 0;
+        cache.assert_within_capacity();
+    }
+
+    #[test]
+    fn test_take_repairs_links() {
+        let mut cache = Cache::new(3);
+        cache.put("A", String::from("value_a"));
+        cache.put("B", String::from("value_b"));
+        cache.put("C", String::from("value_c"));
+        // Cache == [A, B, C]
+
+        assert_eq!(cache.take(&"B"), Some(String::from("value_b")));
+        assert!(!cache.map.contains_key(&"B"));
+        assert_eq!(key_at(&cache, node(&cache, &"C").next), Some("A"));
+        assert_eq!(key_at(&cache, node(&cache, &"A").prev), Some("C"));
+        assert_eq!(recency(&cache), vec!["C", "A"]);
+
+        // Retrait de la tête puis de la queue
+        assert_eq!(cache.take(&"C"), Some(String::from("value_c")));
+        assert_eq!(key_at(&cache, cache.head), Some("A"));
+        assert_eq!(cache.take(&"A"), Some(String::from("value_a")));
+        assert_eq!(cache.head, None);
+        assert_eq!(cache.tail, None);
+        assert_eq!(cache.take(&"A"), None);
+    }
+
+    #[test]
+    fn test_put_with_zero_ttl_does_not_evict() {
+        let mut cache = Cache::new(2);
+        cache.put("A", 1);
+        cache.put("B", 2);
+
+        cache.put_with_ttl("C", 3, Duration::ZERO);
+        assert_eq!(recency(&cache), vec!["B", "A"]);
+        assert_eq!(cache.get(&"C"), None);
+
+        // Une entrée existante n'est ni remplacée, ni retirée, ni promue
+        cache.put_with_ttl("A", 10, Duration::ZERO);
+        assert_eq!(recency(&cache), vec!["B", "A"]);
+        assert_eq!(cache.get(&"A"), Some(&1));
+    }
+
+    #[test]
+    fn test_put_with_max_ttl_never_expires() {
+        let mut cache = Cache::new(2);
+        let now = manual_clock(&mut cache);
+
+        cache.put_with_ttl("A", 1, Duration::MAX);
+        assert_eq!(cache.ttl_remaining(&"A"), None);
+
+        *now.lock().unwrap() += Duration::from_secs(365 * 24 * 3600);
+        assert_eq!(cache.get(&"A"), Some(&1));
+    }
+
+    #[test]
+    fn test_prefetch_loads_only_missing_keys() {
+        let mut cache = Cache::new(4);
+        cache.put("A", 1);
+        cache.put("B", 2);
+
+        let calls = Mutex::new(Vec::new());
+        cache.prefetch(&["A", "C", "B", "D"], |key| {
+            calls.lock().unwrap().push(*key);
+            (*key == "C").then_some(3)
+        });
+
+        // Seules les clés absentes sont chargées, et "D" n'a pas de valeur
+        assert_eq!(*calls.lock().unwrap(), vec!["C", "D"]);
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+        assert_eq!(node(&cache, &"A").value, 1);
+    }
+
+    #[test]
+    fn test_ttl_remaining() {
+        let mut cache = Cache::new(3);
+        let now = manual_clock(&mut cache);
+        cache.put_with_ttl("A", 1, Duration::from_secs(10));
+        cache.put("B", 2);
+
+        *now.lock().unwrap() += Duration::from_secs(4);
+        let remaining = cache.ttl_remaining(&"A").unwrap();
+        assert!(remaining > Duration::from_secs(5) && remaining <= Duration::from_secs(6));
+        assert_eq!(cache.ttl_remaining(&"B"), None);
+        assert_eq!(cache.ttl_remaining(&"X"), None);
+
+        // Une entrée expirée reste visible tant qu'elle n'a pas été consultée
+        *now.lock().unwrap() += Duration::from_secs(6);
+        assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_estimated_heap_size_scales_with_entries() {
+        let sizer = |key: &String, value: &Vec<u8>| key.capacity() + value.capacity();
+        let mut cache: Cache<String, Vec<u8>> = Cache::new(1000);
+        // La place réservée à la création est comptée même sans entrée
+        let empty = cache.estimated_heap_size(sizer);
+        assert!(empty > 0);
+
+        for i in 0..10 {
+            cache.put(format!("{:04}", i), vec![0; 100]);
+        }
+        let small = cache.estimated_heap_size(sizer);
+        assert_eq!(small - empty, 10 * (4 + 100));
+
+        for i in 10..100 {
+            cache.put(format!("{:04}", i), vec![0; 100]);
+        }
+        let large = cache.estimated_heap_size(sizer);
+        assert_eq!(large - empty, 100 * (4 + 100));
+    }
+
+    #[test]
+    fn test_freeze() {
+        let mut cache = Cache::new(3);
+        let now = manual_clock(&mut cache);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put_with_ttl("C", 3, Duration::from_secs(10));
+        cache.get(&"A");
+
+        *now.lock().unwrap() += Duration::from_secs(10);
+        let frozen = cache.freeze();
+        assert_eq!(frozen.get(&"B"), Some(&2));
+        assert_eq!(frozen.peek(&"A"), Some(&1));
+        assert_eq!(frozen.get(&"C"), None);
+        assert_eq!(frozen.get(&"X"), None);
+        assert_eq!(frozen.len(), 3);
+        assert!(!frozen.is_empty());
+
+        // Les lectures n'ont pas modifié l'ordre, et l'entrée expirée est masquée
+        let entries: Vec<_> = frozen.iter().collect();
+        assert_eq!(entries, vec![(&"A", &1), (&"B", &2)]);
+    }
+
+    #[test]
+    fn test_with_seed_is_reproducible() {
+        let run = || {
+            let mut cache = Cache::with_seed(8, 7);
+            let mut results = Vec::new();
+            for i in 0..32u32 {
+                cache.put(i, i * 10);
+                if i % 3 == 0 {
+                    results.push(cache.get(&(i / 2)).copied());
+                }
+            }
+            // L'ordre de parcours de la table dépend directement du hachage
+            let layout: Vec<u32> = cache.map.keys().copied().collect();
+            (results, layout)
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_recency_order() {
+        let mut cache = Cache::new(3);
+        assert!(cache.recency_order().is_empty());
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        assert_eq!(cache.recency_order(), vec!["C", "B", "A"]);
+
+        cache.get(&"A");
+        assert_eq!(cache.recency_order(), vec!["A", "C", "B"]);
+
+        cache.put("D", 4); // Évince "B"
+        assert_eq!(cache.recency_order(), vec!["D", "A", "C"]);
+
+        cache.put("C", 30);
+        cache.remove(&"A");
+        assert_eq!(cache.recency_order(), vec!["C", "D"]);
+    }
+
+    #[test]
+    fn test_ordered_persistence_round_trip() {
+        let filename = "test_cache_ordered.txt";
+        let mut cache: Cache<String, u32> = Cache::new(4);
+        for (i, key) in ["A", "B", "C", "D"].into_iter().enumerate() {
+            cache.put(key.to_string(), i as u32);
+        }
+        cache.get(&"B".to_string());
+        cache.get(&"A".to_string());
+        cache.save_to_file_ordered(filename).unwrap();
+
+        let mut reloaded: Cache<String, u32> = Cache::new(4);
+        reloaded.load_from_file_ordered(filename).unwrap();
+        assert_eq!(recency(&reloaded), recency(&cache));
+        std::fs::remove_file(filename).unwrap();
+
+        // L'ordre des lignes n'a pas d'importance, seuls les rangs comptent
+        let shuffled = "2\tC\t3\n0\tA\t1\nbroken\n1\tB\t2\n";
+        let mut reloaded: Cache<String, u32> = Cache::new(4);
+        reloaded
+            .load_from_reader_ordered(shuffled.as_bytes())
+            .unwrap();
+        assert_eq!(recency(&reloaded), vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_negative_caching() {
+        let mut cache = Cache::new(3);
+        let now = manual_clock(&mut cache);
+        cache.set_negative_ttl(Some(Duration::from_secs(5)));
+        let calls = Mutex::new(0);
+        let fail = || {
+            *calls.lock().unwrap() += 1;
+            None
+        };
+
+        assert_eq!(cache.get_or_try_insert_with("A", fail), None);
+        assert!(cache.is_negative_cached(&"A"));
+        assert_eq!(cache.get_or_try_insert_with("A", fail), None);
+        assert_eq!(*calls.lock().unwrap(), 1);
+        assert!(cache.is_empty());
+
+        // Après l'échéance, le chargeur est rappelé
+        *now.lock().unwrap() += Duration::from_secs(5);
+        assert!(!cache.is_negative_cached(&"A"));
+        assert_eq!(cache.get_or_try_insert_with("A", fail), None);
+        assert_eq!(*calls.lock().unwrap(), 2);
+
+        // Une insertion fait oublier la clé absente
+        cache.put("A", 1);
+        assert!(!cache.is_negative_cached(&"A"));
+        assert_eq!(cache.get_or_try_insert_with("A", fail), Some(&1));
+        assert_eq!(cache.get_or_try_insert_with("B", || Some(2)), Some(&2));
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_convert_policy_lru_to_lfu() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        for _ in 0..5 {
+            cache.get(&"A");
+        }
+
+        cache.convert_policy(EvictionPolicy::Lfu);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+        assert!(cache.slots.iter().flatten().all(|node| node.frequency == 1));
+
+        // Les fréquences repartent de zéro : seuls les accès suivants comptent
+        cache.get(&"B");
+        cache.get(&"B");
+        cache.get(&"C");
+        cache.put("D", 4); // "A" (fréquence 1) est évincé malgré son ancienne popularité
+        assert!(!cache.map.contains_key(&"A"));
+        cache.put("E", 5); // "D" (fréquence 1) est évincé, "C" a été utilisé
+        assert_eq!(recency(&cache), vec!["E", "C", "B"]);
+    }
+
+    #[test]
+    fn test_new_presizes_map() {
+        let mut cache = Cache::new(1000);
+        let reserved = cache.map.capacity();
+        assert!(reserved >= 1000);
+        for i in 0..1000 {
+            cache.put(i, i);
+        }
+        // Aucune réallocation pendant le remplissage
+        assert_eq!(cache.map.capacity(), reserved);
+        assert_eq!(cache.get(&0), Some(&0));
+
+        // Une capacité démesurée n'est pas réservée d'emblée
+        let huge: Cache<u32, u32> = Cache::new(usize::MAX);
+        assert!(huge.map.capacity() < 2 * PRESIZE_LIMIT);
+        assert!(huge.slots.capacity() <= PRESIZE_LIMIT);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_entry_samples_present_entries() {
+        use rand::SeedableRng;
+
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5)] {
+            cache.put(key, value);
+        }
+        cache.remove(&"C");
+        let order = recency(&cache);
+
+        // Générateur à graine fixe : le test est reproductible
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut sampled = HashSet::new();
+        for _ in 0..200 {
+            let (key, value) = cache.random_entry_with(&mut rng).unwrap();
+            assert_eq!(node(&cache, key).value, *value);
+            sampled.insert(*key);
+        }
+        assert_eq!(sampled, HashSet::from(["B", "D", "E"]));
+        assert_eq!(recency(&cache), order);
+        assert!(cache.random_entry().is_some());
+        assert_eq!(Cache::<&str, i32>::new(2).random_entry_with(&mut rng), None);
+    }
+
+    #[test]
+    fn test_load_skips_comments_and_blank_lines() {
+        let filename = "test_cache_comments.txt";
+        std::fs::write(
+            filename,
+            "# Cache annoté à la main\n\nA\t1\n  \n# B\t2\nC\t3\n\t\n#D\t4\n",
+        )
+        .unwrap();
+
+        let mut cache: Cache<String, String> = Cache::new(5);
+        cache.load_from_file(filename).unwrap();
+        assert_eq!(recency(&cache), vec!["C", "A"]);
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_compact_file() {
+        let filename = "test_cache_compact.txt";
+        std::fs::write(filename, "A\t1\nB\t2\ninvalide\nA\t3\nC\t4\nB\t5\n").unwrap();
+
+        Cache::<String, u32>::compact_file(filename).unwrap();
+        let compacted = std::fs::read_to_string(filename).unwrap();
+        assert_eq!(compacted, "#cachelru v1\nA\t3\nC\t4\nB\t5\n");
+
+        // L'en-tête est conservé d'un compactage à l'autre, et une version inconnue refusée
+        Cache::<String, u32>::compact_file(filename).unwrap();
+        assert_eq!(std::fs::read_to_string(filename).unwrap(), compacted);
+        std::fs::write("test_cache_compact_v2.txt", "#cachelru v2\nA\t1\n").unwrap();
+        let error = Cache::<String, u32>::compact_file("test_cache_compact_v2.txt").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        std::fs::remove_file("test_cache_compact_v2.txt").unwrap();
+
+        let mut cache: Cache<String, u32> = Cache::new(3);
+        cache.load_from_file(filename).unwrap();
+        assert_eq!(recency(&cache), vec!["B", "C", "A"]);
+        assert_eq!(node(&cache, &"A".to_string()).value, 3);
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut cache = Cache::new(3);
+        cache.put("A", "a".to_string());
+        cache.put("B", "b".to_string());
+        cache.put("C", "c".to_string());
+
+        let (a, c) = cache.get_disjoint_mut(&"A", &"C").unwrap();
+        std::mem::swap(a, c);
+        a.push('!');
+        assert_eq!(node(&cache, &"A").value, "c!");
+        assert_eq!(node(&cache, &"C").value, "a");
+        // Aucune promotion
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+
+        assert!(cache.get_disjoint_mut(&"A", &"A").is_none());
+        assert!(cache.get_disjoint_mut(&"A", &"X").is_none());
+    }
+
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    fn base64_encode(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        for chunk in bytes.chunks(3) {
+            let n = chunk.iter().fold(0u32, |n, &b| n << 8 | b as u32) << (8 * (3 - chunk.len()));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    fn base64_decode(text: &str) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        for chunk in text.as_bytes().chunks(4) {
+            let digits = chunk.iter().take_while(|&&c| c != b'=').count();
+            let mut n = 0u32;
+            for &c in &chunk[..digits] {
+                n = n << 6 | BASE64.iter().position(|&d| d == c)? as u32;
+            }
+            n <<= 6 * (4 - digits);
+            out.extend((0..digits.saturating_sub(1)).map(|i| (n >> (16 - 8 * i)) as u8));
+        }
+        Some(out)
+    }
+
+    #[test]
+    fn test_save_and_load_with_custom_format() {
+        let filename = "test_cache_base64.txt";
+        let mut cache: Cache<String, Vec<u8>> = Cache::new(3);
+        cache.put("vide".to_string(), vec![]);
+        cache.put("octets".to_string(), vec![0, 9, 10, 255]);
+        cache.put("texte".to_string(), b"tab\tligne\n".to_vec());
+        cache
+            .save_to_file_with(filename, |value| base64_encode(value))
+            .unwrap();
+
+        let content = std::fs::read_to_string(filename).unwrap();
+        assert_eq!(content.lines().nth(1), Some("octets\tAAkK/w=="));
+
+        let mut reloaded: Cache<String, Vec<u8>> = Cache::new(3);
+        reloaded
+            .load_from_file_with(filename, base64_decode)
+            .unwrap();
+        assert_eq!(reloaded.mru_n(3), cache.mru_n(3));
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_count_where() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 5), ("B", 50), ("C", 15), ("D", 8), ("E", 100)] {
+            cache.put(key, value);
+        }
+        cache.remove(&"B");
+        let order = recency(&cache);
+
+        assert_eq!(cache.count_where(|_, value| *value > 10), 2);
+        assert_eq!(cache.count_where(|key, _| *key < "D"), 2);
+        assert_eq!(cache.count_where(|_, _| false), 0);
+        assert_eq!(recency(&cache), order);
+    }
+
+    #[test]
+    fn test_load_from_files_merge_strategies() {
+        let (first, second) = ("test_cache_merge_1.txt", "test_cache_merge_2.txt");
+        std::fs::write(first, "A\t1\nB\t2\n").unwrap();
+        std::fs::write(second, "C\t3\nA\t10\n").unwrap();
+        let files = [first, "test_cache_merge_absent.txt", second];
+
+        let mut keep: Cache<String, u32> = Cache::new(5);
+        keep.load_from_files(&files, MergeStrategy::KeepExisting)
+            .unwrap();
+        assert_eq!(node(&keep, &"A".to_string()).value, 1);
+        assert_eq!(recency(&keep), vec!["C", "B", "A"]);
+
+        let mut overwrite: Cache<String, u32> = Cache::new(5);
+        overwrite
+            .load_from_files(&files, MergeStrategy::Overwrite)
+            .unwrap();
+        assert_eq!(node(&overwrite, &"A".to_string()).value, 10);
+        assert_eq!(recency(&overwrite), vec!["A", "C", "B"]);
+
+        std::fs::remove_file(first).unwrap();
+        std::fs::remove_file(second).unwrap();
+    }
+
+    #[test]
+    fn test_peek_mut_if_keeps_recency() {
+        let mut cache = Cache::with_weigher(3, 100, |_, v: &Vec<u8>| v.len());
+        cache.put("A", vec![1]);
+        cache.put("B", vec![2]);
+        cache.put("C", vec![3]);
+
+        assert!(cache.peek_mut_if(&"A", |v| v.push(10)));
+        assert_eq!(node(&cache, &"A").value, vec![1, 10]);
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+        assert_eq!(cache.total_weight(), 4);
+
+        assert!(!cache.peek_mut_if(&"X", |_| panic!("clé absente")));
+    }
+
+    #[test]
+    fn test_promote_threshold() {
+        let mut cache = Cache::new(3);
+        cache.set_promote_threshold(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+
+        cache.get(&"A");
+        cache.get(&"A");
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+        cache.get(&"A");
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+
+        // Le compteur repart de zéro après la promotion
+        cache.get(&"B");
+        cache.get(&"A");
+        cache.get(&"A");
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+        cache.get(&"B");
+        cache.get(&"B");
+        assert_eq!(recency(&cache), vec!["B", "A", "C"]);
+    }
+
+    #[test]
+    fn test_singleton_keeps_last_entry() {
+        let mut cache = Cache::singleton();
+        cache.put("A", 1);
+        let (evicted, value) = cache.insert_reporting("B", 2);
+        assert_eq!((evicted, *value), (Some(("A", 1)), 2));
+        assert_eq!(cache.get(&"A"), None);
+        assert_eq!(cache.get(&"B"), Some(&2));
+        cache.put("B", 20);
+        cache.put_with_ttl("C", 3, Duration::from_secs(60));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.slots.len(), 1);
+        assert_eq!(recency(&cache), vec!["C"]);
+        assert!(cache.ttl_remaining(&"C").is_some());
+        cache.assert_within_capacity();
+
+        // Même comportement qu'un cache général de capacité 1
+        let mut general = Cache::with_policy(1, EvictionPolicy::Clock);
+        let mut special = Cache::singleton();
+        for key in ["X", "Y", "Y", "Z", "X"] {
+            general.put(key, key.len());
+            special.put(key, key.len());
+            assert_eq!(general.mru_n(1), special.mru_n(1));
+        }
+        assert_eq!(special.remove(&"X"), Some(1));
+        assert!(special.is_empty());
+    }
+
+    /// Clé comptant ses clones, pour vérifier qu'un succès n'en provoque aucun.
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct CountingKey(u32);
+
+    impl Clone for CountingKey {
+        fn clone(&self) -> Self {
+            CLONES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            CountingKey(self.0)
+        }
+    }
+
+    static CLONES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[test]
+    fn test_singleton_keeps_pinned_entry() {
+        let mut cache = Cache::singleton();
+        cache.put("A", 1);
+        assert!(cache.pin(&"A"));
+
+        // L'unique entrée est épinglée : le cache dépasse sa capacité plutôt que de l'évincer
+        let (evicted, value) = cache.insert_reporting("B", 2);
+        assert_eq!((evicted, *value), (None, 2));
+        assert_eq!(cache.get(&"A"), Some(&1));
+        assert_eq!(cache.len(), 2);
+
+        // Une fois désépinglée, "A" redevient évinçable et le cache revient à sa capacité
+        assert!(cache.unpin(&"A"));
+        cache.put("C", 3);
+        assert_eq!(recency(&cache), vec!["C"]);
+        cache.assert_within_capacity();
+    }
+
+    #[test]
+    fn test_get_or_insert_with_ref_clones_only_on_miss() {
+        let clones = || CLONES.load(std::sync::atomic::Ordering::Relaxed);
+        let mut cache = Cache::new(2);
+        let key = CountingKey(1);
+
+        let before = clones();
+        assert_eq!(*cache.get_or_insert_with_ref(&key, || "a"), "a");
+        let after_miss = clones();
+        assert!(after_miss > before);
+
+        for _ in 0..10 {
+            assert_eq!(*cache.get_or_insert_with_ref(&key, || "b"), "a");
+        }
+        assert_eq!(clones(), after_miss);
+        assert_eq!(*cache.get_or_insert_with(CountingKey(2), || "c"), "c");
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_maybe_shrink_when_idle() {
+        let mut cache = Cache::new(1000);
+        let now = manual_clock(&mut cache);
+        let start = *now.lock().unwrap();
+        for i in 0..10 {
+            cache.put(i, i * 10);
+        }
+        for i in 0..5 {
+            cache.remove(&(i * 2));
+        }
+        cache.get(&3);
+
+        // Activité constatée à cet appel : pas de réduction
+        assert!(!cache.maybe_shrink(start));
+        *now.lock().unwrap() += Duration::from_secs(60);
+        assert!(!cache.maybe_shrink(start));
+
+        let before = cache.estimated_heap_size(|_, _| 0);
+        assert!(cache.maybe_shrink(start + Duration::from_secs(30)));
+        assert_eq!(cache.capacity(), 5);
+        assert_eq!(cache.slots.len(), 5);
+        assert!(cache.estimated_heap_size(|_, _| 0) < before);
+        assert_eq!(recency(&cache), vec![3, 9, 7, 5, 1]);
+        assert_eq!(cache.get(&7), Some(&70));
+
+        // Une fois réduit, le cache évince au-delà de sa nouvelle capacité
+        cache.put(11, 110);
+        assert_eq!(cache.len(), 5);
+        assert_eq!(cache.get(&1), None);
+    }
+
+    #[test]
+    fn test_changed_since() {
+        let mut cache = Cache::new(4);
+        assert_eq!(cache.current_generation(), 0);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3)] {
+            cache.put(key, value);
+        }
+        let synced = cache.current_generation();
+        assert_eq!(synced, 3);
+        assert!(cache.changed_since(synced).is_empty());
+
+        // Une lecture n'est pas une modification
+        cache.get(&"A");
+        cache.put("B", 20);
+        cache.update_in_place(&"C", 30).unwrap();
+        cache.put("D", 4);
+        assert_eq!(
+            cache.changed_since(synced),
+            vec![(&"D", &4), (&"B", &20), (&"C", &30)]
+        );
+        assert!(cache.current_generation() > synced);
+        assert_eq!(cache.changed_since(0).len(), 4);
+    }
+
+    #[test]
+    fn test_put_ref() {
+        let mut cache = Cache::new(2);
+        assert_eq!(*cache.put_ref("A", 1), 1);
+        assert_eq!(*cache.put_ref("B", 2), 2);
+        assert_eq!(*cache.put_ref("A", 10), 10);
+        assert_eq!(*cache.put_ref("C", 3), 3); // Évince "B"
+        assert_eq!(recency(&cache), vec!["C", "A"]);
+        assert_eq!(cache.get(&"B"), None);
+    }
+
+    #[test]
+    fn test_structural_events() {
+        let mut cache = Cache::new(3);
+        let events = cache.subscribe();
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3)] {
+            cache.put(key, value);
+        }
+        cache.resize(5);
+        cache.resize(5);
+        cache.resize(2);
+        assert_eq!(recency(&cache), vec!["C", "B"]);
+        cache.clear();
+
+        let received: Vec<CacheEvent> = events.try_iter().collect();
+        assert_eq!(
+            received,
+            vec![
+                CacheEvent::Resize { old: 3, new: 5 },
+                CacheEvent::Resize { old: 5, new: 2 },
+                CacheEvent::Clear,
+            ]
+        );
+
+        // Un abonné parti est oublié
+        drop(events);
+        cache.clear();
+        assert!(cache.subscribers.is_empty());
+    }
+
+    #[test]
+    fn test_validate_under_random_operations() {
+        // En débogage, `put`, `remove` et `resize` vérifient eux-mêmes les invariants
+        for policy in [
+            EvictionPolicy::Lru,
+            EvictionPolicy::Clock,
+            EvictionPolicy::Lfu,
+        ] {
+            let mut cache = Cache::with_policy(8, policy);
+            let mut seed: u64 = 7;
+            for _ in 0..5000 {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let key = (seed >> 33) % 32;
+                match (seed >> 20) % 8 {
+                    0 => cache.resize(1 + (seed >> 40) as usize % 16),
+                    1 | 2 => {
+                        cache.remove(&key);
+                    }
+                    3 => {
+                        cache.get(&key);
+                    }
+                    4 => {
+                        cache.update_in_place(&key, seed).ok();
+                    }
+                    _ => cache.put(key, seed),
+                }
+            }
+            assert_eq!(cache.validate(), Ok(()));
+        }
+
+        // Une corruption est détectée
+        let mut cache = Cache::new(3);
+        cache.insert_entry("A", 1, |_, _| {});
+        cache.insert_entry("B", 2, |_, _| {});
+        let tail = cache.tail.unwrap();
+        cache.node_mut(tail).prev = None;
+        assert!(cache.validate().is_err());
+    }
+
+    #[test]
+    fn test_into_sorted_vec() {
+        let mut cache = Cache::new(5);
+        for key in [3, 1, 5, 4, 2, 6] {
+            cache.put(key, key * 10); // "6" évince "3"
+        }
+        cache.get(&1);
+        cache.remove(&4);
+        assert_eq!(
+            cache.into_sorted_vec(),
+            vec![(1, 10), (2, 20), (5, 50), (6, 60)]
+        );
+    }
+
+    #[test]
+    fn test_contains_and_touch() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+
+        assert!(cache.contains_and_touch(&"A"));
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+        assert!(!cache.contains_and_touch(&"X"));
+        assert_eq!(recency(&cache), vec!["A", "C", "B"]);
+        assert!(!cache.map.contains_key(&"X"));
+    }
+
+    #[test]
+    fn test_pin_predicate_survives_overflow() {
+        for policy in [
+            EvictionPolicy::Lru,
+            EvictionPolicy::Clock,
+            EvictionPolicy::Lfu,
+        ] {
+            let mut cache = Cache::with_policy(3, policy);
+            cache.set_pin_predicate(Box::new(|key: &u32, _: &u32| *key == 0));
+            cache.put(0, 0);
+            for i in 1..100 {
+                cache.put(i, i);
+                assert!(cache.map.contains_key(&0));
+                cache.assert_within_capacity();
+            }
+            assert_eq!(cache.get(&0), Some(&0));
+        }
+
+        // Capacité 1 : l'entrée épinglée n'est jamais remplacée par le raccourci
+        let mut cache = Cache::singleton();
+        cache.set_pin_predicate(Box::new(|key: &u32, _: &u32| *key == 0));
+        cache.put(0, 0);
+        for i in 1..10 {
+            cache.put(i, i);
+            assert_eq!(recency(&cache), vec![i, 0]);
+        }
+
+        // Toutes les entrées épinglées : le cache dépasse sa capacité
+        let mut cache = Cache::new(2);
+        cache.set_pin_predicate(Box::new(|_: &&str, _: &i32| true));
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.put_no_evict("D", 4), Err(("D", 4)));
+    }
+
+    #[test]
+    fn test_pin_and_unpin() {
+        let mut cache = Cache::new(3);
+        cache.put(0, 0);
+        cache.put(1, 1);
+        assert!(cache.pin(&0));
+        assert!(!cache.pin(&42));
+        assert_eq!(cache.pinned_count(), 1);
+
+        let mut evicted = Vec::new();
+        for i in 2..50 {
+            evicted.extend(cache.insert_reporting(i, i).0);
+            assert!(cache.map.contains_key(&0));
+        }
+        assert_eq!(evicted.len(), 47);
+        assert!(evicted.iter().all(|&(key, _)| key != 0));
+        assert_eq!(recency(&cache), vec![49, 48, 0]);
+
+        // Une fois désépinglée, l'entrée redevient la victime naturelle
+        assert!(cache.unpin(&0));
+        assert_eq!(cache.pinned_count(), 0);
+        cache.put(50, 50);
+        assert!(!cache.map.contains_key(&0));
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        let [a, c, d] = cache.get_many_mut([&"A", &"C", &"D"]).unwrap();
+        *a += 10;
+        std::mem::swap(c, d);
+        assert_eq!(node(&cache, &"A").value, 11);
+        assert_eq!(node(&cache, &"C").value, 4);
+        assert_eq!(node(&cache, &"D").value, 3);
+        assert_eq!(recency(&cache), vec!["D", "C", "B", "A"]);
+    }
 
-        let my_value = cache.get(&"D");
-        assert_eq!(my_value, Some(&String::from("value_d")));
-        // Cache == [B, C, D]
+    #[test]
+    fn test_get_many_mut_rejects_overlap_and_missing() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        assert!(cache.get_many_mut([&"A", &"B", &"A"]).is_none());
+        assert!(cache.get_many_mut([&"A", &"X"]).is_none());
+        assert!(cache.get_many_mut::<0>([]).is_some());
+    }
 
-        let my_value = cache.get(&"B");
-        assert_eq!(my_value, Some(&String::from("value_b")));
-        // Cache == [C, D, B]
+    #[test]
+    fn test_retain_recent() {
+        let mut cache = Cache::new(4);
+        let now = manual_clock(&mut cache);
+        cache.set_track_access_times(true);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        *now.lock().unwrap() += Duration::from_secs(30);
+        // "A" est lu, "C" seulement modifié sans compter comme un accès
+        cache.get(&"A");
+        cache.peek_mut_if(&"C", |v| *v += 1);
+        cache.put("D", 4);
+        *now.lock().unwrap() += Duration::from_secs(20);
 
-        let my_value = cache.get(&"C");
-        assert_eq!(my_value, Some(&String::from("value_c")));
-        // Cache == [D, B, C]
+        assert!(cache.retain_recent(Duration::from_secs(40)));
+        assert_eq!(recency(&cache), vec!["D", "A"]);
+        assert_eq!(cache.validate(), Ok(()));
 
-        let my_value = cache.get(&"X");
-        assert_eq!(my_value, None);
-        // Cache == [D, B, C]
+        // Une fenêtre plus longue que l'âge de l'horloge ne supprime rien
+        assert!(cache.retain_recent(Duration::MAX));
+        assert_eq!(cache.len(), 2);
 
-        cache.put("A", String::from("value_a"));
-        // Cache == [B, C, A]
+        // Sans datation, les accès ne consultent pas l'horloge et le balayage est
+        // refusé, sans activer la datation
+        let mut cache = Cache::new(4);
+        let now = manual_clock(&mut cache);
+        cache.put("A", 1);
+        cache.get(&"A");
+        assert_eq!(node(&cache, &"A").last_access, None);
+        *now.lock().unwrap() += Duration::from_secs(60);
+        assert!(!cache.retain_recent(Duration::from_secs(1)));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(node(&cache, &"A").last_access, None);
 
-        cache.put("X", String::from("value_x"));
-        // Cache == [C, A, X]
+        // Une fois activée, les entrées présentes sont datées de l'activation
+        cache.set_track_access_times(true);
+        *now.lock().unwrap() += Duration::from_secs(60);
+        assert!(cache.retain_recent(Duration::from_secs(1)));
+        assert!(cache.is_empty());
+    }
 
-        let my_value = cache.get(&"B");
-        assert_eq!(my_value, None);
-        // Cache == [C, A, X]
+    #[test]
+    fn test_checkpoint_to_file() {
+        let filename = "test_cache_checkpoint.txt";
+        let _ = std::fs::remove_file(filename);
+        let mut cache: Cache<String, u32> = Cache::new(3);
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        cache.checkpoint_to_file(filename).unwrap();
 
-        let my_value = cache.get(&"D");
-        assert_eq!(my_value, None);
-        // Cache == [C, A, X]
+        // Rien n'a changé : le point de contrôle n'ajoute rien
+        cache.checkpoint_to_file(filename).unwrap();
+        assert_eq!(std::fs::read_to_string(filename).unwrap(), "A\t1\nB\t2\n");
+
+        cache.put("A".to_string(), 10);
+        cache.put("C".to_string(), 3);
+        cache.checkpoint_to_file(filename).unwrap();
+        cache.put("B".to_string(), 20);
+        cache.checkpoint_to_file(filename).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(filename).unwrap(),
+            "A\t1\nB\t2\nA\t10\nC\t3\nB\t20\n"
+        );
+
+        // Le `DuplicatePolicy` du cache ne s'applique pas au rejeu des incréments
+        let mut reloaded: Cache<String, u32> = Cache::new(3);
+        reloaded.set_duplicate_policy(DuplicatePolicy::FirstWins);
+        reloaded.load_checkpoints_from_file(filename).unwrap();
+        assert_eq!(recency(&reloaded), vec!["B", "C", "A"]);
+        assert_eq!(node(&reloaded, &"A".to_string()).value, 10);
+        assert_eq!(node(&reloaded, &"B".to_string()).value, 20);
+        assert_eq!(node(&reloaded, &"C".to_string()).value, 3);
+
+        // Le cache rechargé reprend les incréments là où le fichier s'arrête
+        reloaded.checkpoint_to_file(filename).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(filename).unwrap().lines().count(),
+            5
+        );
+        std::fs::remove_file(filename).unwrap();
     }
 
     #[test]
-    fn test_persistent_cache() {
-        let filename = "test_cache.txt";
+    fn test_try_reserve() {
+        let mut cache = Cache::new(1000);
+        cache.put(0, 0);
+        assert!(cache.try_reserve(500).is_ok());
+        assert!(cache.map.capacity() >= 501);
+        assert!(cache.slots.capacity() >= 501);
 
-        {
-            let mut cache: Cache<String, String> = Cache::new_persistent(3, filename);
-            cache.put("A".to_string(), "value_a".to_string());
-            cache.put("B".to_string(), "value_b".to_string());
-            cache.save_to_file(filename).unwrap();
+        // Une réservation démesurée échoue proprement, sans paniquer
+        assert!(cache.try_reserve(usize::MAX).is_err());
+        for i in 1..10 {
+            cache.put(i, i);
         }
+        assert_eq!(cache.len(), 10);
+        assert_eq!(cache.validate(), Ok(()));
+    }
 
-        {
-            let mut cache: Cache<String, String> = Cache::new_persistent(3, filename);
-            assert_eq!(cache.get(&"A".to_string()), Some(&"value_a".to_string()));
-            assert_eq!(cache.get(&"B".to_string()), Some(&"value_b".to_string()));
+    #[test]
+    fn test_swap_values() {
+        let mut cache = Cache::with_weigher(3, 100, |_: &&str, v: &String| v.len());
+        cache.put("A", "a".to_string());
+        cache.put("B", "bbb".to_string());
+        cache.put("C", "c".to_string());
+
+        assert!(cache.swap_values(&"A", &"B"));
+        assert_eq!(node(&cache, &"A").value, "bbb");
+        assert_eq!(node(&cache, &"B").value, "a");
+        assert_eq!(node(&cache, &"A").weight, 3);
+        assert_eq!(cache.total_weight(), 5);
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+
+        // Une clé absente : rien n'est échangé
+        assert!(!cache.swap_values(&"A", &"D"));
+        assert_eq!(node(&cache, &"A").value, "bbb");
+        assert!(cache.swap_values(&"C", &"C"));
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_evict_while() {
+        let mut cache = Cache::new(6);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 9), ("D", 3), ("E", 4)] {
+            cache.put(key, value);
         }
+        cache.pin(&"B");
 
-        // Nettoyage du fichier de test
+        // "B" est épinglée : sautée ; "C" dépasse le seuil et arrête le parcours
+        let evicted = cache.evict_while(|_, v| *v < 5);
+        assert_eq!(evicted, vec![("A", 1)]);
+        assert_eq!(recency(&cache), vec!["E", "D", "C", "B"]);
+
+        // Sans condition bloquante, tout ce qui n'est pas épinglé part
+        assert_eq!(cache.evict_while(|_, _| true).len(), 3);
+        assert_eq!(recency(&cache), vec!["B"]);
+        assert!(cache.evict_while(|_, _| true).is_empty());
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_zero_capacity_then_resize() {
+        let mut cache = Cache::new(0);
+        assert_eq!(cache.map.capacity(), 0);
+        cache.put("A", 1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"A"), None);
+        // Les insertions qui renvoient la valeur ne paniquent pas, sans rien stocker
+        assert_eq!(*cache.put_ref("B", 2), 2);
+        let (evicted, value) = cache.insert_reporting("C", 3);
+        assert!(evicted.is_none());
+        assert_eq!(*value, 3);
+        assert!(cache.is_empty());
+        assert_eq!(cache.validate(), Ok(()));
+
+        cache.resize(3);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        assert_eq!(recency(&cache), vec!["D", "C", "B"]);
+        assert_eq!(cache.get(&"B"), Some(&2));
+        assert_eq!(cache.validate(), Ok(()));
+
+        // Revenir à une capacité nulle vide le cache
+        cache.resize(0);
+        cache.put("E", 5);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_into_parts_from_parts_round_trip() {
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        cache.get(&"B");
+        cache.get(&"A");
+        let order = recency(&cache);
+
+        let (capacity, entries) = cache.into_parts();
+        assert_eq!(capacity, 4);
+        assert_eq!(entries, vec![("A", 1), ("B", 2), ("D", 4), ("C", 3)]);
+
+        let mut rebuilt = Cache::from_parts(capacity, entries);
+        assert_eq!(recency(&rebuilt), order);
+        assert_eq!(rebuilt.validate(), Ok(()));
+        rebuilt.put("E", 5);
+        assert_eq!(rebuilt.get(&"C"), None);
+
+        // Trop d'entrées : seules les plus récentes sont gardées
+        let small = Cache::from_parts(2, vec![("X", 1), ("Y", 2), ("Z", 3)]);
+        assert_eq!(recency(&small), vec!["X", "Y"]);
+
+        // Clé en double : l'occurrence la plus récente l'emporte
+        let primed = Cache::from_parts(3, vec![("A", 1), ("B", 2), ("A", 3)]);
+        assert_eq!(recency(&primed), vec!["A", "B"]);
+        assert_eq!(node(&primed, &"A").value, 1);
+    }
+
+    #[test]
+    fn test_hot_set() {
+        let mut cache = Cache::new(4);
+        for key in ["A", "B", "C", "D"] {
+            cache.put(key, 0);
+        }
+        for _ in 0..3 {
+            cache.get(&"A");
+        }
+        cache.get(&"B");
+        cache.get(&"B");
+        cache.put("C", 1); // Une mise à jour compte comme un accès
+        cache.peek_mut_if(&"D", |v| *v += 1); // Pas celle-ci : aucune promotion
+
+        assert_eq!(cache.hot_set(0).len(), 4);
+        assert_eq!(cache.hot_set(1), vec![&"C", &"B", &"A"]);
+        assert_eq!(cache.hot_set(2), vec![&"B", &"A"]);
+        assert_eq!(cache.hot_set(3), vec![&"A"]);
+        assert!(cache.hot_set(4).is_empty());
+
+        // Le décompte est aussi tenu en mode CLOCK
+        let mut clock = Cache::with_policy(2, EvictionPolicy::Clock);
+        clock.put("X", 0);
+        clock.get(&"X");
+        assert_eq!(clock.hot_set(1), vec![&"X"]);
+    }
+
+    #[test]
+    fn test_demote() {
+        let mut cache = Cache::new(4);
+        for key in ["A", "B", "C", "D"] {
+            cache.put(key, 0);
+        }
+        assert!(cache.demote(&"C"));
+        assert_eq!(recency(&cache), vec!["D", "B", "A", "C"]);
+        assert!(!cache.demote(&"Z"));
+        assert_eq!(cache.validate(), Ok(()));
+
+        cache.put("E", 0);
+        assert_eq!(cache.get(&"C"), None);
+        assert_eq!(recency(&cache), vec!["E", "D", "B", "A"]);
+
+        // La tête elle-même peut être rétrogradée
+        assert!(cache.demote(&"E"));
+        assert_eq!(recency(&cache), vec!["D", "B", "A", "E"]);
+        assert_eq!(cache.validate(), Ok(()));
+
+        // En mode LFU, l'entrée rétrogradée passe devant les moins fréquentes
+        let mut lfu = Cache::with_policy(2, EvictionPolicy::Lfu);
+        lfu.put("X", 0);
+        lfu.put("Y", 0);
+        lfu.get(&"X");
+        lfu.demote(&"X");
+        lfu.put("Z", 0);
+        assert_eq!(lfu.get(&"X"), None);
+        assert_eq!(lfu.get(&"Y"), Some(&0));
+    }
+
+    #[test]
+    fn test_autogrow() {
+        let mut cache = Cache::new(4);
+        let events = cache.subscribe();
+        cache.set_autogrow(10, 2.0);
+
+        // Remplissage : le cache n'est pas encore plein, rien n'est observé
+        for i in 0..4 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.capacity(), 4);
+
+        // Des mises à jour sur un cache plein ne sont pas des défauts
+        for _ in 0..3 {
+            for i in 0..4 {
+                cache.put(i, i + 1);
+            }
+        }
+        assert_eq!(cache.capacity(), 4);
+
+        // Quatre nouvelles clés d'affilée sur un cache plein : la capacité double
+        for i in 4..8 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.capacity(), 8);
+        assert_eq!(events.try_recv(), Ok(CacheEvent::Resize { old: 4, new: 8 }));
+
+        // Défauts soutenus : la croissance plafonne à la capacité maximale
+        for i in 8..100 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.capacity(), 10);
+        assert_eq!(cache.len(), 10);
+        assert_eq!(
+            events.try_recv(),
+            Ok(CacheEvent::Resize { old: 8, new: 10 })
+        );
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut primary = Cache::new(5);
+        let mut replica = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            primary.put(key, value);
+        }
+        for (key, value) in [("B", 2), ("C", 30), ("E", 5), ("A", 10), ("F", 6)] {
+            replica.put(key, value);
+        }
+
+        let diff = primary.diff(&replica);
+        assert_eq!(diff.only_in_self, vec![&"D"]);
+        assert_eq!(diff.only_in_other, vec![&"F", &"E"]);
+        assert_eq!(diff.differing, vec![(&"C", &3, &30), (&"A", &1, &10)]);
+
+        // Lecture seule : l'ordre d'utilisation est intact
+        assert_eq!(recency(&primary), vec!["D", "C", "B", "A"]);
+
+        let same = primary.diff(&primary);
+        assert!(same.only_in_self.is_empty() && same.only_in_other.is_empty());
+        assert!(same.differing.is_empty());
+    }
+
+    #[test]
+    fn test_bloom_filter_has_no_false_negatives() {
+        let mut cache = Cache::with_seed(500, 7);
+        for i in 0..100 {
+            cache.put(i, i);
+        }
+        // Les clés déjà présentes sont reprises à l'activation
+        cache.set_bloom_filter(Some(5_000));
+        for i in 100..3_000 {
+            cache.put(i, i);
+            if i % 3 == 0 {
+                cache.remove(&(i - 1));
+            }
+        }
+        for i in 0..3_000 {
+            let present = cache.map.contains_key(&i);
+            assert_eq!(cache.get(&i).is_some(), present, "clé {}", i);
+        }
+
+        // Le filtre écarte l'essentiel des absences
+        let bloom = cache.bloom.as_ref().unwrap();
+        let hasher = cache.map.hasher();
+        let maybe = (10_000..20_000)
+            .filter(|key| bloom.may_contain(hasher.hash_one(key)))
+            .count();
+        assert!(maybe < 300, "{} faux positifs", maybe);
+
+        cache.clear();
+        cache.put(1, 1);
+        assert_eq!(cache.get(&1), Some(&1));
+        assert_eq!(cache.get(&2), None);
+        cache.set_bloom_filter(None);
+        assert_eq!(cache.get(&1), Some(&1));
+    }
+
+    /// Clé dont l'égalité et le hachage ignorent l'étiquette.
+    #[derive(Debug, Clone)]
+    struct TaggedKey {
+        id: u32,
+        tag: &'static str,
+    }
+
+    impl PartialEq for TaggedKey {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for TaggedKey {}
+
+    impl Hash for TaggedKey {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.id.hash(state);
+        }
+    }
+
+    #[test]
+    fn test_get_entry_returns_stored_key() {
+        let mut cache = Cache::new(2);
+        cache.put(
+            TaggedKey {
+                id: 1,
+                tag: "canonique",
+            },
+            "a",
+        );
+        cache.put(
+            TaggedKey {
+                id: 2,
+                tag: "autre",
+            },
+            "b",
+        );
+
+        let lookup = TaggedKey {
+            id: 1,
+            tag: "recherche",
+        };
+        let (key, value) = cache.get_entry(&lookup).unwrap();
+        assert_eq!(key.tag, "canonique");
+        assert_eq!(*value, "a");
+
+        // L'entrée a été promue : c'est l'autre qui est évincée
+        cache.put(TaggedKey { id: 3, tag: "" }, "c");
+        assert!(cache.get_entry(&lookup).is_some());
+        assert!(cache.get_entry(&TaggedKey { id: 2, tag: "" }).is_none());
+    }
+
+    #[test]
+    fn test_put_with_priority() {
+        let mut cache = Cache::new(4);
+        cache.put_with_priority("H1", 1, 2);
+        cache.put("L1", 2);
+        cache.put_with_priority("H2", 3, 2);
+        cache.put("L2", 4);
+
+        // Les entrées de priorité 0 partent d'abord, de la moins récente à la plus récente
+        cache.put_with_priority("M", 5, 1);
+        assert_eq!(recency(&cache), vec!["M", "L2", "H2", "H1"]);
+        cache.put("X", 6);
+        assert_eq!(recency(&cache), vec!["X", "M", "H2", "H1"]);
+        cache.put_with_priority("Y", 7, 1);
+        assert_eq!(recency(&cache), vec!["Y", "M", "H2", "H1"]);
+
+        // Plus de priorité 0 : la moins récente des priorités 1 est évincée
+        cache.put("Z", 8);
+        assert_eq!(recency(&cache), vec!["Z", "Y", "H2", "H1"]);
+
+        // Une mise à jour par `put` conserve la priorité
+        cache.put("H1", 10);
+        cache.put_with_priority("U", 9, 2);
+        cache.put_with_priority("V", 11, 2);
+        assert_eq!(recency(&cache), vec!["V", "U", "H1", "H2"]);
+        cache.put_with_priority("W", 12, 2);
+        assert_eq!(recency(&cache), vec!["W", "V", "U", "H1"]);
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_with_temporary_capacity() {
+        let mut cache = Cache::new(3);
+        let events = cache.subscribe();
+        cache.put("A", 1);
+        cache.put("B", 2);
+
+        let evicted_during_burst = cache.with_temporary_capacity(6, |cache| {
+            assert_eq!(cache.capacity(), 6);
+            for (key, value) in [("C", 3), ("D", 4), ("E", 5), ("F", 6)] {
+                cache.put(key, value);
+            }
+            cache.get(&"A");
+            6 - cache.len()
+        });
+        assert_eq!(evicted_during_burst, 0);
+
+        // Au retour, seules les trois entrées les plus récentes restent
+        assert_eq!(cache.capacity(), 3);
+        assert_eq!(recency(&cache), vec!["A", "F", "E"]);
+        assert_eq!(events.try_recv(), Ok(CacheEvent::Resize { old: 3, new: 6 }));
+        assert_eq!(events.try_recv(), Ok(CacheEvent::Resize { old: 6, new: 3 }));
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_drain_expired() {
+        let mut cache = Cache::new(5);
+        let now = manual_clock(&mut cache);
+        cache.put_with_ttl("A", 1, Duration::from_secs(10));
+        cache.put("B", 2);
+        cache.put_with_ttl("C", 3, Duration::from_secs(30));
+        cache.put_with_ttl("D", 4, Duration::from_secs(5));
+        cache.put("E", 5);
+        assert!(cache.drain_expired().is_empty());
+
+        *now.lock().unwrap() += Duration::from_secs(10);
+        assert_eq!(cache.drain_expired(), vec![("D", 4), ("A", 1)]);
+        assert_eq!(recency(&cache), vec!["E", "C", "B"]);
+        assert_eq!(cache.validate(), Ok(()));
+
+        *now.lock().unwrap() += Duration::from_secs(20);
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(recency(&cache), vec!["E", "B"]);
+    }
+
+    #[test]
+    fn test_zero_capacity_get_or_insert() {
+        let mut cache = Cache::new(0);
+        let mut calls = 0;
+        let mut load = |value| {
+            calls += 1;
+            value
+        };
+        assert_eq!(*cache.get_or_insert_with("A", || load(1)), 1);
+        assert_eq!(*cache.get_or_insert_with("A", || load(2)), 2);
+        assert_eq!(calls, 2);
+        assert_eq!(*cache.get_or_insert_with_ref(&"B", || 3), 3);
+        assert_eq!(cache.get_or_try_insert_with("C", || Some(4)), Some(&4));
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"A"), None);
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_get_or_refresh() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", -2);
+        cache.put("C", 3);
+        let is_stale = |value: &i32| *value < 0;
+
+        assert_eq!(*cache.get_or_refresh("B", is_stale, || 20), 20);
+        assert_eq!(recency(&cache), vec!["B", "C", "A"]);
+        assert_eq!(*cache.get_or_refresh("A", is_stale, || unreachable!()), 1);
+        assert_eq!(recency(&cache), vec!["A", "B", "C"]);
+        assert_eq!(*cache.get_or_refresh("D", is_stale, || 4), 4);
+        assert_eq!(recency(&cache), vec!["D", "A", "B"]);
+        assert_eq!(cache.get(&"B"), Some(&20));
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_intern() {
+        let mut interner: Cache<Arc<str>, ()> = Cache::new(2);
+        let a = interner.intern(Arc::from("a"));
+        let b = interner.intern(Arc::from("b"));
+        assert!(Arc::ptr_eq(&a, &interner.intern(Arc::from("a"))));
+        assert_eq!(interner.len(), 2);
+
+        // "b" est le handle le plus froid : il laisse sa place à "c"
+        interner.intern(Arc::from("c"));
+        let b2 = interner.intern(Arc::from("b"));
+        assert!(!Arc::ptr_eq(&b, &b2));
+        assert_eq!(&*b, &*b2);
+        assert!(Arc::ptr_eq(&b2, &interner.intern(Arc::from("b"))));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_flush_dirty() {
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        cache.flush_dirty(|_, _| {});
+
+        cache.put("B", 20);
+        cache.get(&"A");
+        assert!(cache.peek_mut_if(&"D", |v| *v += 40));
+        let mut written = Vec::new();
+        cache.flush_dirty(|key, value| written.push((*key, *value)));
+        assert_eq!(written, vec![("D", 44), ("B", 20)]);
+
+        let mut calls = 0;
+        cache.flush_dirty(|_, _| calls += 1);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_debug_output() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.get(&"A");
+        let debug = format!("{cache:?}");
+        assert_eq!(
+            debug,
+            r#"Cache { capacity: 3, len: 2, entries: {"A": 1, "B": 2} }"#
+        );
+        for internal in ["Node", "prev", "next"] {
+            assert!(!debug.contains(internal));
+        }
+    }
+
+    #[test]
+    fn test_get_or_insert_with_key() {
+        let mut cache: Cache<String, usize> = Cache::new(2);
+        let mut loaded = Vec::new();
+        for key in ["un", "trois", "un"] {
+            let value = *cache.get_or_insert_with_key(key.to_string(), |key| {
+                loaded.push(key.clone());
+                key.len()
+            });
+            assert_eq!(value, key.len());
+        }
+        assert_eq!(loaded, vec!["un", "trois"]);
+        assert_eq!(recency(&cache), vec!["un", "trois"]);
+    }
+
+    #[test]
+    fn test_watermarks() {
+        let mut cache = Cache::new(10);
+        cache.set_watermarks(6, 3);
+        for i in 0..6 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.len(), 6);
+
+        // Le seuil haut est atteint : éviction en une rafale jusqu'au seuil bas
+        cache.put(6, 6);
+        assert_eq!(recency(&cache), vec![6, 5, 4, 3]);
+
+        // Puis plus aucune éviction jusqu'au prochain passage du seuil haut
+        cache.put(7, 7);
+        cache.put(8, 8);
+        cache.put(3, 30);
+        assert_eq!(recency(&cache), vec![3, 8, 7, 6, 5, 4]);
+        cache.put(9, 9);
+        assert_eq!(recency(&cache), vec![9, 3, 8, 7]);
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_peek_or() {
+        let mut cache = Cache::new(3);
+        let now = manual_clock(&mut cache);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put_with_ttl("C", 3, Duration::from_secs(10));
+        let fallback = 0;
+
+        assert_eq!(*cache.peek_or(&"A", &fallback), 1);
+        assert_eq!(*cache.peek_or(&"X", &fallback), 0);
+        assert_eq!(*cache.peek_or(&"C", &fallback), 3);
+        *now.lock().unwrap() += Duration::from_secs(10);
+        assert_eq!(*cache.peek_or(&"C", &fallback), 0);
+
+        // Ni promotion ni insertion
+        assert_eq!(recency(&cache), vec!["C", "B", "A"]);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_rebuild_links() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        let a = cache.map[&"A"];
+        let c = cache.map[&"C"];
+        cache.node_mut(a).next = Some(c);
+        cache.node_mut(c).prev = None;
+        cache.head = Some(c);
+        assert!(cache.validate().is_err());
+
+        // "X" est absent et ignoré ; "A" et "B", non cités, passent en queue
+        cache.rebuild_links(&["C", "X", "D", "C"]);
+        assert_eq!(cache.validate(), Ok(()));
+        assert_eq!(recency(&cache), vec!["C", "D", "A", "B"]);
+        cache.put("E", 5);
+        cache.put("F", 6);
+        assert_eq!(recency(&cache), vec!["F", "E", "C", "D", "A"]);
+    }
+
+    #[test]
+    fn test_link_report() {
+        let mut cache = Cache::new(3);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3)] {
+            cache.put(key, value);
+        }
+        assert_eq!(
+            cache.link_report(),
+            "tête → queue : [\"C\", \"B\", \"A\"]\n\
+             queue → tête : [\"A\", \"B\", \"C\"]\n\
+             liens cohérents : les deux parcours voient les 3 entrées en ordre inverse"
+        );
+
+        // Lien `next` coupé au milieu de la liste
+        let b = cache.map[&"B"];
+        cache.node_mut(b).next = None;
+        let report = cache.link_report();
+        assert!(report.contains("tête → queue : [\"C\", \"B\"]\n"));
+        assert!(report.contains("anomalie : tête → queue : 2 entrées parcourues pour 3"));
+        assert!(report.contains("anomalie : les deux parcours ne sont pas l'inverse"));
+    }
+
+    #[test]
+    fn test_retain_most_recent() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5)] {
+            cache.put(key, value);
+        }
+        cache.get(&"B");
+        assert_eq!(
+            cache.retain_most_recent(2),
+            vec![("D", 4), ("C", 3), ("A", 1)]
+        );
+        assert_eq!(recency(&cache), vec!["B", "E"]);
+        assert!(cache.retain_most_recent(2).is_empty());
+        assert_eq!(cache.capacity(), 5);
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_eviction_channel() {
+        let mut cache = Cache::new(2);
+        let evictions = cache.eviction_channel();
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        cache.get(&"B");
+        cache.put_with_ttl("D", 4, Duration::from_secs(60));
+        cache.remove(&"B");
+        cache.put("E", 5);
+        cache.put("F", 6);
+        cache.resize(1);
+        assert_eq!(
+            evictions.try_iter().collect::<Vec<_>>(),
+            vec![("A", 1), ("C", 3), ("D", 4), ("E", 5)]
+        );
+
+        // L'éviction rendue par `insert_reporting` ne passe pas par le canal
+        let (evicted, _) = cache.insert_reporting("G", 7);
+        assert_eq!(evicted, Some(("F", 6)));
+        assert!(evictions.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_resize_evicts_coldest_and_keeps_order() {
+        let mut cache = Cache::new(5);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5)] {
+            cache.put(key, value);
+        }
+        cache.get(&"B");
+        cache.get(&"D");
+        let evictions = cache.eviction_channel();
+
+        cache.resize(2);
+        assert_eq!(
+            evictions.try_iter().collect::<Vec<_>>(),
+            vec![("A", 1), ("C", 3), ("E", 5)]
+        );
+        assert_eq!(recency(&cache), vec!["D", "B"]);
+
+        cache.resize(10);
+        assert_eq!(recency(&cache), vec!["D", "B"]);
+        assert!(evictions.try_recv().is_err());
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_get_batch() {
+        let mut cache = Cache::new(4);
+        for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+            cache.put(key, value);
+        }
+        assert_eq!(
+            cache.get_batch(&["B", "X", "A", "B"]),
+            vec![("B", Some(2)), ("X", None), ("A", Some(1)), ("B", Some(2))]
+        );
+        assert_eq!(recency(&cache), vec!["B", "A", "D", "C"]);
+        assert!(cache.get_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_reset_stats_on_resize() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.get(&"A");
+        cache.get(&"B");
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // Par défaut, les statistiques survivent au changement de capacité
+        cache.resize(5);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        cache.set_reset_stats_on_resize(true);
+        cache.resize(5);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+        cache.resize(2);
+        assert_eq!(cache.stats(), CacheStats::default());
+        cache.get(&"A");
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 0 });
+    }
+
+    #[test]
+    fn test_stats_count_only_lookups() {
+        let mut cache = Cache::new(3);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        assert!(cache.peek_mut_if(&"A", |v| *v += 1));
+        assert!(!cache.peek_mut_if(&"X", |v| *v += 1));
+        assert!(cache.pin(&"A"));
+        assert!(cache.unpin(&"A"));
+        assert!(!cache.pin(&"X"));
+        assert_eq!(cache.update_in_place(&"B", 20), Ok(2));
+        assert!(cache.touch(&"B"));
+        assert!(cache.swap_values(&"A", &"B"));
+        cache.prefetch(&["A", "C"], |_| Some(3));
+        assert_eq!(cache.stats(), CacheStats::default());
+
+        cache.get(&"A");
+        cache.get(&"X");
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+    }
+
+    #[test]
+    fn test_insert_at() {
+        let mut cache = Cache::new(5);
+        cache.insert_at(3, "A", 1);
+        cache.insert_at(0, "B", 2);
+        cache.insert_at(1, "C", 3);
+        assert_eq!(recency(&cache), vec!["B", "C", "A"]);
+        cache.insert_at(3, "D", 4);
+        cache.insert_at(2, "E", 5);
+        assert_eq!(recency(&cache), vec!["B", "C", "E", "A", "D"]);
+
+        // Cache plein : la queue ("D") est évincée avant le placement
+        cache.insert_at(9, "F", 6);
+        assert_eq!(recency(&cache), vec!["B", "C", "E", "A", "F"]);
+        // Une clé présente est déplacée au rang demandé
+        cache.insert_at(4, "B", 20);
+        assert_eq!(recency(&cache), vec!["C", "E", "A", "F", "B"]);
+        assert_eq!(cache.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_evictions_for() {
+        let mut cache = Cache::new(4);
+        cache.put("A", 1);
+        cache.put("B", 2);
+        assert_eq!(cache.evictions_for(0), 0);
+        assert_eq!(cache.evictions_for(1), 0);
+        assert_eq!(cache.evictions_for(2), 0);
+        assert_eq!(cache.evictions_for(3), 1);
+        assert_eq!(cache.evictions_for(10), 8);
+        assert_eq!(cache.evictions_for(usize::MAX), usize::MAX - 4);
+
+        // L'estimation correspond aux évictions effectives
+        let evictions = cache.eviction_channel();
+        for key in ["C", "D", "E"] {
+            cache.put(key, 0);
+        }
+        assert_eq!(evictions.try_iter().count(), 1);
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_logging_evictions_and_misses() {
+        use std::sync::Mutex;
+        use std::thread::{self, ThreadId};
+
+        /// Journal capturant les traces, par fil d'exécution : les tests tournent
+        /// en parallèle et partagent le même journal global.
+        struct Capture(Mutex<Vec<(ThreadId, log::Level, String)>>);
+
+        impl log::Log for Capture {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+            fn log(&self, record: &log::Record) {
+                let entry = (
+                    thread::current().id(),
+                    record.level(),
+                    record.args().to_string(),
+                );
+                self.0.lock().unwrap().push(entry);
+            }
+            fn flush(&self) {}
+        }
+
+        static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let mut cache = Cache::new(2);
+        cache.log_keys();
+        cache.put("A", 1);
+        cache.put("B", 2);
+        cache.put("C", 3);
+        cache.get(&"A");
+        let mut singleton = Cache::singleton();
+        singleton.put("X", 1);
+        singleton.put("Y", 2);
+
+        let current = thread::current().id();
+        let records: Vec<(log::Level, String)> = CAPTURE
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(thread, _, _)| *thread == current)
+            .map(|(_, level, message)| (*level, message.clone()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                (log::Level::Trace, "entrée évincée : \"A\"".to_string()),
+                (
+                    log::Level::Debug,
+                    "clé absente du cache : \"A\"".to_string()
+                ),
+                (log::Level::Trace, "entrée évincée : _".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_file_round_trip() {
+        let filename = "test_cache_async.txt";
+        let mut cache: Cache<String, u32> = Cache::new(3);
+        cache.put("A".to_string(), 1);
+        cache.put("B".to_string(), 2);
+        cache.get(&"A".to_string());
+        cache.save_to_file_async(filename).await.unwrap();
+
+        // Même format que `save_to_file` : relisible par la version bloquante
+        let mut reloaded: Cache<String, u32> = Cache::new(3);
+        reloaded.load_from_file_async(filename).await.unwrap();
+        assert_eq!(recency(&reloaded), vec!["A", "B"]);
+        let mut blocking: Cache<String, u32> = Cache::new(3);
+        blocking.load_from_file(filename).unwrap();
+        assert_eq!(recency(&blocking), recency(&reloaded));
+
+        std::fs::write(filename, "#cachelru v2\nA\t1\n").unwrap();
+        let error = reloaded.load_from_file_async(filename).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
         std::fs::remove_file(filename).unwrap();
+
+        // Fichier absent : rien n'est chargé
+        let mut empty: Cache<String, u32> = Cache::new(3);
+        empty.load_from_file_async(filename).await.unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_with_initial_entries() {
+        let entries = vec![("A", 1), ("B", 2), ("C", 3), ("D", 4)];
+        let mut cache = Cache::with_initial_entries(3, entries);
+        assert_eq!(cache.recency_order(), vec!["A", "B", "C"]);
+        assert_eq!(cache.capacity(), 3);
+
+        cache.put("E", 5); // Évince "C", la moins récente
+        assert_eq!(cache.recency_order(), vec!["E", "A", "B"]);
+        assert_eq!(cache.get(&"C"), None);
     }
 }
@@ -0,0 +1,172 @@
+use crate::cache::{Cache, LRUCache};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+type GroupFn<K, G> = dyn Fn(&K) -> G + Send + Sync;
+
+/// Un cache LRU doté d'un index secondaire par groupe, permettant d'invalider
+/// d'un coup toutes les entrées d'un même groupe (par exemple toutes les clés
+/// `(region, id)` d'une même région) sans parcourir tout le cache.
+///
+/// L'index est tenu à jour lors des insertions, des suppressions et des évictions.
+pub struct GroupedCache<K: Eq + Hash + Clone, V, G> {
+    cache: Cache<K, V>,
+    group_of: Box<GroupFn<K, G>>,
+    groups: HashMap<G, HashSet<K>>,
+}
+
+impl<K: Eq + Hash + Clone, V, G: Eq + Hash> GroupedCache<K, V, G> {
+    /// Crée un nouveau `GroupedCache` dont le groupe de chaque clé est donné par `group_of`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::LRUCache;
+    /// use cachelru::grouped::GroupedCache;
+    ///
+    /// let mut cache = GroupedCache::new(10, |key: &(&str, u32)| key.0);
+    /// cache.put(("eu", 1), "a");
+    /// cache.put(("us", 1), "b");
+    /// assert_eq!(cache.invalidate_group(&"eu"), 1);
+    /// assert_eq!(cache.get(&("eu", 1)), None);
+    /// ```
+    pub fn new<F>(capacity: usize, group_of: F) -> Self
+    where
+        F: Fn(&K) -> G + Send + Sync + 'static,
+    {
+        GroupedCache {
+            cache: Cache::new(capacity),
+            group_of: Box::new(group_of),
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Supprime une entrée du cache et renvoie sa valeur.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.cache.remove(key)?;
+        Self::unindex(&mut self.groups, &self.group_of, key);
+        Some(value)
+    }
+
+    /// Supprime toutes les entrées du groupe et renvoie leur nombre.
+    pub fn invalidate_group(&mut self, group: &G) -> usize {
+        let keys = match self.groups.remove(group) {
+            Some(keys) => keys,
+            None => return 0,
+        };
+        keys.iter()
+            .filter(|key| self.cache.remove(key).is_some())
+            .count()
+    }
+
+    /// Renvoie le nombre d'entrées présentes dans le cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Donne accès en lecture au cache sous-jacent.
+    pub fn cache(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+
+    /// Retire une clé de l'index de son groupe. Ne prend que l'index, pour pouvoir
+    /// être appelée pendant qu'une valeur du cache est empruntée.
+    fn unindex(groups: &mut HashMap<G, HashSet<K>>, group_of: &dyn Fn(&K) -> G, key: &K) {
+        let group = group_of(key);
+        if let Some(keys) = groups.get_mut(&group) {
+            keys.remove(key);
+            if keys.is_empty() {
+                groups.remove(&group);
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, G: Eq + Hash> LRUCache<K, V> for GroupedCache<K, V, G> {
+    /// Insère une paire clé-valeur et met l'index à jour, évictions comprises.
+    fn put(&mut self, key: K, value: V) {
+        // Le cache n'est limité qu'en nombre d'entrées : au plus une éviction
+        let (evicted, _) = self.cache.insert_reporting(key.clone(), value);
+        if let Some((evicted_key, _)) = evicted {
+            Self::unindex(&mut self.groups, &self.group_of, &evicted_key);
+        }
+        self.groups
+            .entry((self.group_of)(&key))
+            .or_default()
+            .insert(key);
+    }
+
+    /// Récupère une valeur du cache par sa clé.
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let value = self.cache.get(key);
+        if value.is_none() {
+            // L'entrée a pu expirer entre-temps : l'index ne doit pas la garder
+            Self::unindex(&mut self.groups, &self.group_of, key);
+        }
+        value
+    }
+}
+
+impl<K, V, G> fmt::Debug for GroupedCache<K, V, G>
+where
+    K: Eq + Hash + Clone + fmt::Debug,
+    V: fmt::Debug,
+    G: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupedCache")
+            .field("cache", &self.cache)
+            .field("groups", &self.groups)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidate_group() {
+        let mut cache = GroupedCache::new(4, |key: &(&str, u32)| key.0);
+        cache.put(("eu", 1), "a");
+        cache.put(("eu", 2), "b");
+        cache.put(("us", 1), "c");
+        cache.put(("us", 2), "d");
+
+        assert_eq!(cache.invalidate_group(&"eu"), 2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&("eu", 1)), None);
+        assert_eq!(cache.get(&("us", 1)), Some(&"c"));
+        assert_eq!(cache.invalidate_group(&"eu"), 0);
+
+        // Une éviction retire aussi la clé de l'index
+        cache.put(("asia", 1), "e");
+        cache.put(("asia", 2), "f");
+        cache.put(("asia", 3), "g");
+        assert!(!cache.groups[&"us"].contains(&("us", 2)));
+        assert_eq!(cache.invalidate_group(&"us"), 1);
+
+        // Une suppression aussi
+        assert_eq!(cache.remove(&("asia", 1)), Some("e"));
+        assert_eq!(cache.groups[&"asia"].len(), 2);
+        assert_eq!(cache.invalidate_group(&"asia"), 2);
+        assert!(cache.is_empty());
+        assert!(cache.groups.is_empty());
+    }
+
+    #[test]
+    fn test_get_looks_up_once() {
+        let mut cache = GroupedCache::new(4, |key: &(&str, u32)| key.0);
+        cache.put(("eu", 1), "a");
+        assert_eq!(cache.get(&("eu", 1)), Some(&"a"));
+        assert_eq!(cache.get(&("eu", 2)), None);
+        let stats = cache.cache().stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+}
@@ -0,0 +1,113 @@
+//! Persistance au format JSON Lines : un objet `{"key":...,"value":...}` par ligne,
+//! avec `serde` (fonctionnalité `serde`). Contrairement à un tableau JSON unique,
+//! le flux peut être complété par ajout et relu au fil de l'eau.
+
+use crate::cache::{Cache, LRUCache};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+
+/// Ligne écrite par `write_jsonl`.
+#[derive(Serialize)]
+struct EntryRef<'a, K, V> {
+    key: &'a K,
+    value: &'a V,
+}
+
+/// Ligne relue par `read_jsonl`.
+#[derive(Deserialize)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K: Eq + Hash + Clone, V> Cache<K, V> {
+    /// Écrit le cache au format JSON Lines, de l'entrée la moins récemment utilisée
+    /// à la plus récente : le flux peut être complété par ajout et rechargé avec
+    /// `read_jsonl` en conservant l'ordre d'utilisation.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::cache::{Cache, LRUCache};
+    ///
+    /// let mut cache: Cache<String, i32> = Cache::new(3);
+    /// cache.put("A".to_string(), 1);
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// cache.write_jsonl(&mut buffer).unwrap();
+    /// assert_eq!(buffer, b"{\"key\":\"A\",\"value\":1}\n");
+    /// ```
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        for (key, value) in self.lru_n(self.len()) {
+            serde_json::to_writer(&mut writer, &EntryRef { key, value })?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+
+    /// Charge le cache depuis un flux JSON Lines produit par `write_jsonl`.
+    ///
+    /// Les lignes vides ou mal formées sont ignorées, comme avec `load_from_reader`.
+    pub fn read_jsonl<R: BufRead>(&mut self, reader: R) -> io::Result<()>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        for line in reader.lines() {
+            let line = line?;
+            if let Ok(Entry { key, value }) = serde_json::from_str::<Entry<K, V>>(&line) {
+                self.put(key, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonl_round_trip() {
+        let mut cache: Cache<String, Vec<(u32, String)>> = Cache::new(3);
+        cache.put("A".to_string(), vec![(1, "simple".to_string())]);
+        cache.put(
+            "B \"quoted\"".to_string(),
+            vec![
+                (2, "tab\there\nnewline \\ é".to_string()),
+                (3, "😀".to_string()),
+            ],
+        );
+        cache.put("C".to_string(), vec![(4, "\u{1}".to_string())]);
+        cache.get(&"A".to_string());
+
+        let mut buffer: Vec<u8> = Vec::new();
+        cache.write_jsonl(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert_eq!(text.lines().count(), 3);
+        assert_eq!(
+            text.lines().last(),
+            Some("{\"key\":\"A\",\"value\":[[1,\"simple\"]]}")
+        );
+
+        let mut reloaded: Cache<String, Vec<(u32, String)>> = Cache::new(3);
+        reloaded.read_jsonl(buffer.as_slice()).unwrap();
+        assert_eq!(reloaded.mru_n(3), cache.mru_n(3));
+    }
+
+    #[test]
+    fn test_read_jsonl_skips_invalid_lines() {
+        let data = "{ \"value\" : 1, \"key\" : \"A\" }\n\nnot json\n{\"key\":\"B\"}\n{\"key\":\"\\ud83d\\ude00\",\"value\":2}\n";
+        let mut cache: Cache<String, u32> = Cache::new(3);
+        cache.read_jsonl(data.as_bytes()).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"A".to_string()), Some(&1));
+        assert_eq!(cache.get(&"😀".to_string()), Some(&2));
+    }
+}
@@ -1 +1,10 @@
+pub mod arc;
+pub mod binary;
+mod bloom;
 pub mod cache;
+pub mod grouped;
+#[cfg(feature = "serde")]
+mod jsonl;
+pub mod memo;
+pub mod sharded;
+pub mod sync;
@@ -0,0 +1,86 @@
+use crate::cache::Cache;
+use std::fmt;
+use std::hash::Hash;
+
+/// Une fonction mémoïsée : chaque résultat est gardé dans un cache LRU borné, et
+/// `f` n'est rappelée pour une clé qu'après l'éviction de son résultat.
+///
+/// Obtenue avec [`memoize`].
+pub struct Memoizer<K: Eq + Hash + Clone, V, F> {
+    cache: Cache<K, V>,
+    f: F,
+}
+
+/// Mémoïse `f` en gardant au plus `capacity` résultats, les moins récemment
+/// demandés étant évincés les premiers.
+///
+/// # Exemple
+///
+/// ```
+/// use cachelru::memo::memoize;
+///
+/// let mut square = memoize(100, |n: u64| n * n);
+/// assert_eq!(*square.call(12), 144);
+/// assert_eq!(*square.call(12), 144); // Résultat déjà calculé
+/// ```
+pub fn memoize<K, V, F>(capacity: usize, f: F) -> Memoizer<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    F: FnMut(K) -> V,
+{
+    Memoizer {
+        cache: Cache::new(capacity),
+        f,
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, F: FnMut(K) -> V> Memoizer<K, V, F> {
+    /// Renvoie le résultat de `f` pour `key`, calculé seulement s'il n'est pas déjà
+    /// en cache. Le résultat devient le plus récemment utilisé.
+    pub fn call(&mut self, key: K) -> &V {
+        let f = &mut self.f;
+        self.cache.get_or_insert_with_key(key, |key| f(key.clone()))
+    }
+
+    /// Renvoie le cache des résultats, par exemple pour en consulter la taille.
+    pub fn cache(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K, V, F> fmt::Debug for Memoizer<K, V, F>
+where
+    K: Eq + Hash + Clone + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memoizer")
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memoize_computes_once_per_key() {
+        let mut calls = Vec::new();
+        let mut length = memoize(2, |word: &str| {
+            calls.push(word);
+            word.len()
+        });
+        assert_eq!(*length.call("un"), 2);
+        assert_eq!(*length.call("deux"), 4);
+        assert_eq!(*length.call("un"), 2);
+        assert_eq!(length.cache().len(), 2);
+
+        // "deux" est le résultat le plus froid : il est évincé puis recalculé
+        assert_eq!(*length.call("trois"), 5);
+        assert_eq!(*length.call("un"), 2);
+        assert_eq!(*length.call("deux"), 4);
+        drop(length);
+        assert_eq!(calls, vec!["un", "deux", "trois", "deux"]);
+    }
+}
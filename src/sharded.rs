@@ -0,0 +1,144 @@
+use crate::cache::{Cache, LRUCache};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Un cache LRU partageable entre threads, découpé en fragments indépendants
+/// protégés chacun par son propre verrou : deux clés de fragments différents
+/// peuvent être lues ou écrites en parallèle.
+///
+/// L'ordre d'utilisation, et donc l'éviction, est propre à chaque fragment.
+#[derive(Debug)]
+pub struct ShardedCache<K: Eq + Hash + Clone, V> {
+    shards: Vec<Shard<K, V>>,
+    hasher: RandomState,
+}
+
+#[derive(Debug)]
+struct Shard<K: Eq + Hash + Clone, V> {
+    cache: Mutex<Cache<K, V>>,
+    len: AtomicUsize, // Nombre d'entrées du fragment, relevé après chaque opération
+}
+
+impl<K: Eq + Hash + Clone, V> ShardedCache<K, V> {
+    /// Crée un nouveau `ShardedCache` de `shards` fragments (au moins un) se
+    /// partageant une capacité totale de `capacity` entrées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::sharded::ShardedCache;
+    ///
+    /// let cache = ShardedCache::new(100, 4);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get(&"A"), Some(1));
+    /// ```
+    pub fn new(capacity: usize, shards: usize) -> Self {
+        let shards = shards.max(1);
+        let per_shard = capacity.div_ceil(shards);
+        ShardedCache {
+            shards: (0..shards)
+                .map(|_| Shard {
+                    cache: Mutex::new(Cache::new(per_shard)),
+                    len: AtomicUsize::new(0),
+                })
+                .collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Insère une paire clé-valeur dans le fragment de la clé.
+    pub fn put(&self, key: K, value: V) {
+        self.with_shard(&key, |cache| cache.put(key.clone(), value));
+    }
+
+    /// Récupère une copie de la valeur associée à la clé.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.with_shard(key, |cache| cache.get(key).cloned())
+    }
+
+    /// Supprime une entrée et renvoie sa valeur.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.with_shard(key, |cache| cache.remove(key))
+    }
+
+    /// Renvoie le nombre exact d'entrées, en verrouillant tour à tour chaque fragment.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.cache.lock().unwrap().len())
+            .sum()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Renvoie une estimation du nombre d'entrées sans prendre aucun verrou, à partir
+    /// des compteurs tenus par chaque fragment.
+    ///
+    /// Sans accès concurrent, la valeur est exacte. Pendant des écritures
+    /// concurrentes, elle peut refléter un état intermédiaire (certains fragments
+    /// avant, d'autres après leur dernière opération) : elle convient aux métriques,
+    /// pas aux décisions qui exigent un compte exact, pour lesquelles `len` reste
+    /// disponible.
+    pub fn approx_len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.len.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Exécute `f` sur le cache du fragment de la clé, puis relève sa taille.
+    fn with_shard<R>(&self, key: &K, f: impl FnOnce(&mut Cache<K, V>) -> R) -> R {
+        let index = (self.hasher.hash_one(key) % self.shards.len() as u64) as usize;
+        let shard = &self.shards[index];
+        let mut cache = shard.cache.lock().unwrap();
+        let result = f(&mut cache);
+        shard.len.store(cache.len(), Ordering::Relaxed);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_approx_len_matches_len() {
+        let cache = ShardedCache::new(400, 4);
+        assert_eq!(cache.approx_len(), 0);
+        for i in 0..40 {
+            cache.put(i, i);
+        }
+        cache.remove(&3);
+        cache.remove(&100);
+        assert_eq!(cache.len(), 39);
+        assert_eq!(cache.approx_len(), cache.len());
+
+        // Une fois les écritures concurrentes terminées, l'estimation redevient exacte
+        let cache = Arc::new(ShardedCache::new(1000, 8));
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let cache = Arc::clone(&cache);
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        cache.put(t * 100 + i, i);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(cache.approx_len(), cache.len());
+        assert_eq!(cache.get(&250), Some(50));
+    }
+}
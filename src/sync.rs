@@ -0,0 +1,128 @@
+use crate::cache::{Cache, LRUCache};
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard};
+
+/// Un cache LRU partageable entre threads, protégé par un verrou unique.
+///
+/// Contrairement à `ShardedCache`, toutes les opérations passent par le même
+/// verrou : l'ordre d'utilisation est global et un instantané du cache est cohérent.
+#[derive(Debug)]
+pub struct SyncCache<K: Eq + Hash + Clone, V> {
+    inner: Mutex<Cache<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V> SyncCache<K, V> {
+    /// Crée un nouveau `SyncCache` avec une capacité donnée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use cachelru::sync::SyncCache;
+    ///
+    /// let cache = SyncCache::new(3);
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get(&"A"), Some(1));
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        SyncCache::from_cache(Cache::new(capacity))
+    }
+
+    /// Partage un cache existant, en conservant sa configuration.
+    pub fn from_cache(cache: Cache<K, V>) -> Self {
+        SyncCache {
+            inner: Mutex::new(cache),
+        }
+    }
+
+    /// Insère une paire clé-valeur dans le cache.
+    pub fn put(&self, key: K, value: V) {
+        self.lock().put(key, value);
+    }
+
+    /// Récupère une copie de la valeur associée à la clé.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lock().get(key).cloned()
+    }
+
+    /// Supprime une entrée et renvoie sa valeur.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.lock().remove(key)
+    }
+
+    /// Renvoie le nombre d'entrées présentes dans le cache.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Renvoie une copie de toutes les entrées, de la plus récemment utilisée à la
+    /// moins récemment utilisée. Le verrou n'est pris qu'une fois, le temps de la
+    /// copie : l'instantané est cohérent, et l'appelant peut le parcourir sans
+    /// bloquer les autres threads.
+    pub fn snapshot_entries(&self) -> Vec<(K, V)>
+    where
+        V: Clone,
+    {
+        let cache = self.lock();
+        cache
+            .mru_n(cache.len())
+            .into_iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Verrouille le cache pour accéder à toutes ses opérations.
+    pub fn lock(&self) -> MutexGuard<'_, Cache<K, V>> {
+        self.inner.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_snapshot_entries_under_concurrent_writes() {
+        let cache = Arc::new(SyncCache::new(50));
+        let writers: Vec<_> = (0..4u64)
+            .map(|t| {
+                let cache = Arc::clone(&cache);
+                thread::spawn(move || {
+                    for i in 0..2000 {
+                        let key = (t * 7919 + i * 31) % 200;
+                        cache.put(key, key * 2);
+                        if i % 5 == 0 {
+                            cache.remove(&((key + 1) % 200));
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for _ in 0..200 {
+            let snapshot = cache.snapshot_entries();
+            // Chaque instantané reflète un état réel du cache
+            assert!(snapshot.len() <= 50);
+            let keys: HashSet<u64> = snapshot.iter().map(|&(key, _)| key).collect();
+            assert_eq!(keys.len(), snapshot.len());
+            assert!(snapshot.iter().all(|&(key, value)| value == key * 2));
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let snapshot = cache.snapshot_entries();
+        assert_eq!(snapshot.len(), cache.len());
+        assert_eq!(snapshot[0].0, *cache.lock().mru_n(1)[0].0);
+    }
+}